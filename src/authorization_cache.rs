@@ -0,0 +1,325 @@
+//! Authorization Cache of a Charge Point
+//!
+//! [AuthorizationCache] remembers the [IdTagInfo] received for each identifier in Authorize.conf, StartTransaction.conf and
+//! StopTransaction.conf, so that identifiers can be authorized locally while the Charge Point is offline. Together with the
+//! [LocalAuthList], which takes precedence over the cache, it implements the local authorization rules of the
+//! AuthorizationCacheEnabled, LocalAuthorizeOffline and AllowOfflineTxForUnknownId configuration keys.
+//!
+//! Identifiers known in the Local Authorization List are not added to the cache. An entry whose expiry date has passed is
+//! treated as Expired. When the cache is full, entries which are no longer valid are evicted first, then the least recently
+//! used ones.
+//!
+//! Identifiers sharing a parent id tag form a group: a transaction started by one member may be stopped by another, see
+//! [AuthorizationCache::is_same_group].
+//!
+//! # Example
+//! ```
+//! # fn ocpp_authorization_cache_example() {
+//! use ocpp::*;
+//!
+//! let mut cache = AuthorizationCache::new(100);
+//! let res = AuthorizeResponse {
+//!     id_tag_info: IdTagInfo {
+//!         expiry_date: None,
+//!         parent_id_tag: Some(String::from("FLEET")),
+//!         status: AuthorizationStatus::Accepted,
+//!     },
+//! };
+//! cache.authorize_response("TAG", &res, None, chrono::Utc::now());
+//!
+//! // Later, while offline
+//! let authorization = cache.authorize_offline("TAG", None, chrono::Utc::now());
+//! assert!(authorization.is_allowed());
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    AuthorizationStatus, AuthorizeResponse, ClearCacheResponse, IdTagInfo,
+    LocalAuthList, SimpleStatus, StartTransactionResponse,
+    StopTransactionResponse,
+};
+
+/// Name of the configuration key enabling the Authorization Cache
+pub const AUTHORIZATION_CACHE_ENABLED: &str = "AuthorizationCacheEnabled";
+
+/// Name of the configuration key allowing transactions for locally authorized identifiers while offline
+pub const LOCAL_AUTHORIZE_OFFLINE: &str = "LocalAuthorizeOffline";
+
+/// Name of the configuration key allowing transactions for unknown identifiers while offline
+pub const ALLOW_OFFLINE_TX_FOR_UNKNOWN_ID: &str = "AllowOfflineTxForUnknownId";
+
+/// Outcome of authorizing an identifier locally
+#[derive(Debug, Clone, PartialEq)]
+pub enum LocalAuthorization {
+    /// Valid in the Local Authorization List or Authorization Cache
+    Authorized(IdTagInfo),
+    /// Known, but blocked, expired or invalid. The status is Expired if the expiry date has passed.
+    NotAuthorized(IdTagInfo),
+    /// Unknown, but allowed by AllowOfflineTxForUnknownId
+    AllowedUnknown,
+    /// Unknown, or known while LocalAuthorizeOffline is disabled
+    Unknown,
+}
+
+impl LocalAuthorization {
+    /// Whether a transaction may be started
+    pub fn is_allowed(&self) -> bool {
+        matches!(
+            self,
+            LocalAuthorization::Authorized(_)
+                | LocalAuthorization::AllowedUnknown
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    info: IdTagInfo,
+    last_used: u64,
+}
+
+/// Authorization Cache of a Charge Point. See the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct AuthorizationCache {
+    entries: HashMap<String, CacheEntry>,
+    capacity: usize,
+    uses: u64,
+    enabled: bool,
+    local_authorize_offline: bool,
+    allow_offline_tx_for_unknown_id: bool,
+}
+
+impl AuthorizationCache {
+    /// Create an enabled cache holding up to `capacity` identifiers, authorizing known identifiers offline and rejecting
+    /// unknown ones
+    pub fn new(capacity: usize) -> Self {
+        AuthorizationCache {
+            entries: HashMap::new(),
+            capacity,
+            uses: 0,
+            enabled: true,
+            local_authorize_offline: true,
+            allow_offline_tx_for_unknown_id: false,
+        }
+    }
+
+    /// Set AuthorizationCacheEnabled. A disabled cache stores nothing and is not used to authorize. Defaults to true.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.set_enabled(enabled);
+        self
+    }
+
+    /// Set LocalAuthorizeOffline. Defaults to true.
+    pub fn local_authorize_offline(mut self, enabled: bool) -> Self {
+        self.local_authorize_offline = enabled;
+        self
+    }
+
+    /// Set AllowOfflineTxForUnknownId. Defaults to false.
+    pub fn allow_offline_tx_for_unknown_id(mut self, enabled: bool) -> Self {
+        self.allow_offline_tx_for_unknown_id = enabled;
+        self
+    }
+
+    /// Change AuthorizationCacheEnabled, e.g. after a ChangeConfiguration.req. Disabling the cache clears it.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.entries.clear();
+        }
+    }
+
+    /// Number of cached identifiers
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    /// Whether no identifiers are cached
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// Cached information of an identifier, as received
+    pub fn get(&self, id_tag: &str) -> Option<&IdTagInfo> {
+        self.entries.get(id_tag).map(|entry| &entry.info)
+    }
+
+    /// Store the information received for an identifier, unless it is known in `local_list`
+    pub fn update(
+        &mut self,
+        id_tag: &str,
+        info: &IdTagInfo,
+        local_list: Option<&LocalAuthList>,
+        now: DateTime<Utc>,
+    ) {
+        if !self.enabled
+            || self.capacity == 0
+            || local_list.is_some_and(|list| list.get(id_tag).is_some())
+        {
+            return;
+        }
+        if !self.entries.contains_key(id_tag)
+            && self.entries.len() >= self.capacity
+        {
+            self.evict(now);
+        }
+        self.uses += 1;
+        self.entries.insert(
+            String::from(id_tag),
+            CacheEntry {
+                info: info.clone(),
+                last_used: self.uses,
+            },
+        );
+    }
+
+    /// Store the result of an Authorize.req for `id_tag`
+    pub fn authorize_response(
+        &mut self,
+        id_tag: &str,
+        res: &AuthorizeResponse,
+        local_list: Option<&LocalAuthList>,
+        now: DateTime<Utc>,
+    ) {
+        self.update(id_tag, &res.id_tag_info, local_list, now);
+    }
+
+    /// Store the result of a StartTransaction.req for `id_tag`
+    pub fn start_transaction_response(
+        &mut self,
+        id_tag: &str,
+        res: &StartTransactionResponse,
+        local_list: Option<&LocalAuthList>,
+        now: DateTime<Utc>,
+    ) {
+        self.update(id_tag, &res.id_tag_info, local_list, now);
+    }
+
+    /// Store the result of a StopTransaction.req for `id_tag`, if it contains one
+    pub fn stop_transaction_response(
+        &mut self,
+        id_tag: &str,
+        res: &StopTransactionResponse,
+        local_list: Option<&LocalAuthList>,
+        now: DateTime<Utc>,
+    ) {
+        if let Some(info) = &res.id_tag_info {
+            self.update(id_tag, info, local_list, now);
+        }
+    }
+
+    /// Answer a ClearCache.req by removing all identifiers
+    pub fn clear_cache(&mut self) -> ClearCacheResponse {
+        self.entries.clear();
+        ClearCacheResponse {
+            status: SimpleStatus::Accepted,
+        }
+    }
+
+    /// Authorize an identifier while offline, from `local_list` if it is known there, otherwise from the cache
+    pub fn authorize_offline(
+        &mut self,
+        id_tag: &str,
+        local_list: Option<&LocalAuthList>,
+        now: DateTime<Utc>,
+    ) -> LocalAuthorization {
+        let info = match local_list.and_then(|list| list.get(id_tag)) {
+            Some(info) => Some(info.clone()),
+            None => self.touch(id_tag).cloned(),
+        };
+
+        match info {
+            Some(_) if !self.local_authorize_offline => {
+                LocalAuthorization::Unknown
+            },
+            Some(info) => {
+                let info = effective(info, now);
+                if is_valid(&info) {
+                    LocalAuthorization::Authorized(info)
+                } else {
+                    LocalAuthorization::NotAuthorized(info)
+                }
+            },
+            None if self.allow_offline_tx_for_unknown_id => {
+                LocalAuthorization::AllowedUnknown
+            },
+            None => LocalAuthorization::Unknown,
+        }
+    }
+
+    /// Parent id tag of an identifier, from `local_list` if it is known there, otherwise from the cache
+    pub fn parent_id_tag<'a>(
+        &'a self,
+        id_tag: &str,
+        local_list: Option<&'a LocalAuthList>,
+    ) -> Option<&'a str> {
+        local_list
+            .and_then(|list| list.get(id_tag))
+            .or_else(|| self.get(id_tag))
+            .and_then(|info| info.parent_id_tag.as_deref())
+    }
+
+    /// Whether two identifiers belong to the same group, i.e. are equal, share a parent id tag, or one is the parent id tag of
+    /// the other. A transaction may be stopped by any member of the group which started it, and a group member may be refused
+    /// a new transaction with ConcurrentTx while another member has one.
+    pub fn is_same_group(
+        &self,
+        id_tag: &str,
+        other_id_tag: &str,
+        local_list: Option<&LocalAuthList>,
+    ) -> bool {
+        if id_tag == other_id_tag {
+            return true;
+        }
+        let parent = self.parent_id_tag(id_tag, local_list);
+        let other_parent = self.parent_id_tag(other_id_tag, local_list);
+        (parent.is_some() && parent == other_parent)
+            || parent == Some(other_id_tag)
+            || other_parent == Some(id_tag)
+    }
+
+    fn touch(&mut self, id_tag: &str) -> Option<&IdTagInfo> {
+        if !self.enabled {
+            return None;
+        }
+        self.uses += 1;
+        let uses = self.uses;
+        self.entries.get_mut(id_tag).map(|entry| {
+            entry.last_used = uses;
+            &entry.info
+        })
+    }
+
+    /// Remove the least recently used entry, preferring entries which are no longer valid
+    fn evict(&mut self, now: DateTime<Utc>) {
+        let victim = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| {
+                (
+                    is_valid(&effective(entry.info.clone(), now)),
+                    entry.last_used,
+                )
+            })
+            .map(|(id_tag, _)| id_tag.clone());
+        if let Some(id_tag) = victim {
+            self.entries.remove(&id_tag);
+        }
+    }
+}
+
+/// Information with the status changed to Expired if its expiry date has passed
+fn effective(mut info: IdTagInfo, now: DateTime<Utc>) -> IdTagInfo {
+    if info.expiry_date.is_some_and(|expiry| expiry <= now) {
+        info.status = AuthorizationStatus::Expired;
+    }
+    info
+}
+
+/// Whether an identifier may start a transaction. ConcurrentTx identifiers are valid, only not for another transaction.
+fn is_valid(info: &IdTagInfo) -> bool {
+    matches!(
+        info.status,
+        AuthorizationStatus::Accepted | AuthorizationStatus::ConcurrentTx
+    )
+}
@@ -0,0 +1,311 @@
+//! Charging profiles installed on a Charge Point
+//!
+//! [ChargingProfileStore] applies SetChargingProfile.req and ClearChargingProfile.req as a Charge Point would. A new profile
+//! replaces an installed one with the same id, or with the same purpose and stack level on the same connector, and is
+//! rejected if it breaks a rule of [SetChargingProfileRequest::check], exceeds the ChargeProfileMaxStackLevel or would
+//! install more than MaxChargingProfilesInstalled profiles. TxProfiles only apply to the transaction active on their connector
+//! and are discarded when it ends.
+//!
+//! Together with a [CompositeScheduleCalculator], the store answers GetCompositeSchedule.req from the profiles which apply to
+//! a connector.
+//!
+//! # Example
+//! ```
+//! # fn ocpp_charging_profile_store_example() {
+//! use ocpp::*;
+//!
+//! let mut store = ChargingProfileStore::default().charge_profile_max_stack_level(8);
+//! let profile = ChargingProfile::builder(ChargingRateUnit::A)
+//!     .id(1)
+//!     .stack_level(0)
+//!     .transaction_id(42)
+//!     .add_period(0, 16.0, None)
+//!     .build();
+//! let req = SetChargingProfileRequest {
+//!     connector_id: 1,
+//!     cs_charging_profiles: profile,
+//! };
+//! // Transaction 42 is active on connector 1
+//! assert_eq!(store.set_charging_profile(&req, Some(42)).status, ChargingProfileStatus::Accepted);
+//!
+//! store.transaction_ended(42);
+//! assert!(store.is_empty());
+//! # }
+//! ```
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    ChargingProfile, ChargingProfilePurpose, ChargingProfileStatus,
+    ClearChargeProfileStatus, ClearChargingProfileRequest,
+    ClearChargingProfileResponse, CompositeScheduleCalculator,
+    GetCompositeScheduleRequest, GetCompositeScheduleResponse,
+    SetChargingProfileRequest, SetChargingProfileResponse, SimpleStatus,
+};
+
+/// A charging profile installed on a connector
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstalledProfile {
+    /// Connector of the profile, 0 for the whole Charge Point
+    pub connector_id: u32,
+    /// The profile
+    pub profile: ChargingProfile,
+}
+
+/// Charging profiles installed on a Charge Point. See the [module documentation](self).
+#[derive(Debug, Clone, Default)]
+pub struct ChargingProfileStore {
+    installed: Vec<InstalledProfile>,
+    charge_profile_max_stack_level: Option<u32>,
+    max_charging_profiles_installed: Option<usize>,
+    number_of_connectors: Option<u32>,
+}
+
+impl ChargingProfileStore {
+    /// Reject profiles above the value of ChargeProfileMaxStackLevel. Unlimited by default.
+    pub fn charge_profile_max_stack_level(
+        mut self,
+        max_stack_level: u32,
+    ) -> Self {
+        self.charge_profile_max_stack_level = Some(max_stack_level);
+        self
+    }
+
+    /// Limit the number of installed profiles to the value of MaxChargingProfilesInstalled. Unlimited by default.
+    pub fn max_charging_profiles_installed(
+        mut self,
+        max_profiles: usize,
+    ) -> Self {
+        self.max_charging_profiles_installed = Some(max_profiles);
+        self
+    }
+
+    /// Reject profiles and composite schedules for connectors above the value of NumberOfConnectors. Unlimited by default.
+    pub fn number_of_connectors(mut self, number_of_connectors: u32) -> Self {
+        self.number_of_connectors = Some(number_of_connectors);
+        self
+    }
+
+    /// Installed profiles, in the order in which they were installed
+    pub fn iter(&self) -> impl Iterator<Item = &InstalledProfile> {
+        self.installed.iter()
+    }
+
+    /// Installed profile with the given id
+    pub fn get(&self, charging_profile_id: u32) -> Option<&InstalledProfile> {
+        self.installed
+            .iter()
+            .find(|p| p.profile.charging_profile_id == charging_profile_id)
+    }
+
+    /// Number of installed profiles
+    pub fn len(&self) -> usize { self.installed.len() }
+
+    /// Whether no profiles are installed
+    pub fn is_empty(&self) -> bool { self.installed.is_empty() }
+
+    /// Apply a SetChargingProfile.req. `active_transaction` is the transaction active on the connector of the request, to
+    /// which a TxProfile must apply.
+    pub fn set_charging_profile(
+        &mut self,
+        req: &SetChargingProfileRequest,
+        active_transaction: Option<u32>,
+    ) -> SetChargingProfileResponse {
+        SetChargingProfileResponse {
+            status: self.install(req, active_transaction),
+        }
+    }
+
+    /// Apply a ClearChargingProfile.req. A request with an id clears only that profile, otherwise all profiles matching the
+    /// connector, purpose and stack level of the request are cleared.
+    pub fn clear_charging_profile(
+        &mut self,
+        req: &ClearChargingProfileRequest,
+    ) -> ClearChargingProfileResponse {
+        let matches = |installed: &InstalledProfile| {
+            let profile = &installed.profile;
+            match req.id {
+                Some(id) => profile.charging_profile_id == id,
+                None => {
+                    req.connector_id
+                        .map_or(true, |c| c == installed.connector_id)
+                        && req
+                            .charging_profile_purpose
+                            .as_ref()
+                            .map_or(true, |p| {
+                                *p == profile.charging_profile_purpose
+                            })
+                        && req
+                            .stack_level
+                            .map_or(true, |s| s == profile.stack_level)
+                },
+            }
+        };
+        let before = self.installed.len();
+        self.installed.retain(|installed| !matches(installed));
+        ClearChargingProfileResponse {
+            status: if self.installed.len() < before {
+                ClearChargeProfileStatus::Accepted
+            } else {
+                ClearChargeProfileStatus::Unknown
+            },
+        }
+    }
+
+    /// Discard the TxProfiles of a transaction which ended
+    pub fn transaction_ended(&mut self, transaction_id: u32) {
+        self.installed.retain(|installed| {
+            installed.profile.charging_profile_purpose
+                != ChargingProfilePurpose::TxProfile
+                || installed.profile.transaction_id != Some(transaction_id)
+        });
+    }
+
+    /// Profiles which apply to a connector: the ChargePointMaxProfiles, the TxDefaultProfiles of the connector, those of
+    /// connector 0 unless the connector has one at the same stack level, and the TxProfiles of the connector
+    pub fn profiles_for_connector(
+        &self,
+        connector_id: u32,
+    ) -> Vec<&ChargingProfile> {
+        let own_default_levels: Vec<u32> = self
+            .on_connector(connector_id)
+            .filter(|p| {
+                p.charging_profile_purpose
+                    == ChargingProfilePurpose::TxDefaultProfile
+            })
+            .map(|p| p.stack_level)
+            .collect();
+        self.installed
+            .iter()
+            .filter(|installed| {
+                let profile = &installed.profile;
+                match profile.charging_profile_purpose {
+                    ChargingProfilePurpose::ChargePointMaxProfile => true,
+                    ChargingProfilePurpose::TxDefaultProfile => {
+                        installed.connector_id == connector_id
+                            || (installed.connector_id == 0
+                                && !own_default_levels
+                                    .contains(&profile.stack_level))
+                    },
+                    ChargingProfilePurpose::TxProfile => {
+                        installed.connector_id == connector_id
+                    },
+                }
+            })
+            .map(|installed| &installed.profile)
+            .collect()
+    }
+
+    /// Answer a GetCompositeSchedule.req received at `now` with the composite schedule of the profiles applying to its
+    /// connector, or Rejected for an unknown connector
+    pub fn get_composite_schedule(
+        &self,
+        req: &GetCompositeScheduleRequest,
+        calculator: &CompositeScheduleCalculator,
+        now: DateTime<Utc>,
+    ) -> GetCompositeScheduleResponse {
+        if !self.is_known_connector(req.connector_id) {
+            return GetCompositeScheduleResponse {
+                status: SimpleStatus::Rejected,
+                connector_id: None,
+                schedule_start: None,
+                charging_schedule: None,
+            };
+        }
+        let profiles: Vec<ChargingProfile> = self
+            .profiles_for_connector(req.connector_id)
+            .into_iter()
+            .cloned()
+            .collect();
+        calculator.get_composite_schedule(req, &profiles, now)
+    }
+
+    fn install(
+        &mut self,
+        req: &SetChargingProfileRequest,
+        active_transaction: Option<u32>,
+    ) -> ChargingProfileStatus {
+        let profile = &req.cs_charging_profiles;
+        if let Err(err) = req.check() {
+            tracing::warn!("OCPP Charging profile rejected: {}", err);
+            return ChargingProfileStatus::Rejected;
+        }
+        if !self.is_known_connector(req.connector_id) {
+            tracing::warn!(
+                "OCPP Charging profile for unknown connector {}",
+                req.connector_id
+            );
+            return ChargingProfileStatus::Rejected;
+        }
+        if profile.charging_profile_purpose
+            == ChargingProfilePurpose::ChargePointMaxProfile
+            && req.connector_id != 0
+        {
+            tracing::warn!("OCPP ChargePointMaxProfile not set on connector 0");
+            return ChargingProfileStatus::Rejected;
+        }
+        if profile.charging_profile_purpose == ChargingProfilePurpose::TxProfile
+            && (active_transaction.is_none()
+                || profile.transaction_id != active_transaction)
+        {
+            tracing::warn!(
+                "OCPP TxProfile for a transaction which is not active on connector {}",
+                req.connector_id
+            );
+            return ChargingProfileStatus::Rejected;
+        }
+        if self
+            .charge_profile_max_stack_level
+            .is_some_and(|max| profile.stack_level > max)
+        {
+            tracing::warn!(
+                "OCPP Charging profile stack level {} exceeds ChargeProfileMaxStackLevel",
+                profile.stack_level
+            );
+            return ChargingProfileStatus::Rejected;
+        }
+
+        let replaces = |installed: &InstalledProfile| {
+            installed.profile.charging_profile_id == profile.charging_profile_id
+                || (installed.connector_id == req.connector_id
+                    && installed.profile.charging_profile_purpose
+                        == profile.charging_profile_purpose
+                    && installed.profile.stack_level == profile.stack_level)
+        };
+        let remaining = self
+            .installed
+            .iter()
+            .filter(|installed| !replaces(installed))
+            .count();
+        if self
+            .max_charging_profiles_installed
+            .is_some_and(|max| remaining >= max)
+        {
+            tracing::warn!(
+                "OCPP Charging profile exceeds MaxChargingProfilesInstalled"
+            );
+            return ChargingProfileStatus::Rejected;
+        }
+
+        self.installed.retain(|installed| !replaces(installed));
+        self.installed.push(InstalledProfile {
+            connector_id: req.connector_id,
+            profile: profile.clone(),
+        });
+        ChargingProfileStatus::Accepted
+    }
+
+    fn on_connector(
+        &self,
+        connector_id: u32,
+    ) -> impl Iterator<Item = &ChargingProfile> {
+        self.installed
+            .iter()
+            .filter(move |installed| installed.connector_id == connector_id)
+            .map(|installed| &installed.profile)
+    }
+
+    fn is_known_connector(&self, connector_id: u32) -> bool {
+        self.number_of_connectors.map_or(true, |n| connector_id <= n)
+    }
+}
@@ -0,0 +1,428 @@
+//! WebSocket charge point client
+//!
+//! Enabled with the `client` feature. [ChargePoint] connects to a Central System with the `ocpp1.6` subprotocol and keeps the
+//! connection alive:
+//! 1. on connect, a BootNotification.req is sent and repeated at the interval given by the Central System until it is accepted,
+//! 2. once accepted, a Heartbeat.req is sent at the interval from the BootNotification.conf,
+//! 3. when the connection is lost, it is re-established after an exponential [Backoff] with jitter.
+//!
+//! Calls from the Central System are answered by a [ChargePointHandler]. Calls which are only sent by a Charge Point are rejected
+//! with [OCPPCallErrorCode::NotSupported](crate::OCPPCallErrorCode::NotSupported) without reaching the handler. Further charge
+//! point initiated calls, such as StatusNotification.req, are sent through a [ChargePointHandle].
+//!
+//! The charge point authenticates itself according to the [security profiles](crate::security): with HTTP Basic authentication
+//! when an [AuthorizationKey] is set, and over TLS, optionally with a client certificate, when [ChargePoint::tls] is set.
+//!
+//! # Example
+//! ```no_run
+//! # async fn ocpp_client_example() {
+//! use ocpp::client::ChargePoint;
+//! use ocpp::*;
+//!
+//! struct MyHandler {}
+//! impl ChargePointHandler for MyHandler {
+//!     fn reset(&mut self, _req: ResetRequest) -> Result<ResetResponse, OCPPCallErrorCode> {
+//!         Ok(ResetResponse { status: SimpleStatus::Accepted })
+//!     }
+//! }
+//!
+//! let boot_notification = BootNotificationRequest {
+//!     charge_point_vendor: String::from("Example"),
+//!     charge_point_model: String::from("Model 1"),
+//!     charge_point_serial_number: None,
+//!     charge_box_serial_number: None,
+//!     firmware_version: None,
+//!     iccid: None,
+//!     imsi: None,
+//!     meter_type: None,
+//!     meter_serial_number: None,
+//! };
+//! let charge_point = ChargePoint::new("ws://localhost:9000/ocpp/CP001", boot_notification, MyHandler {});
+//! let handle = charge_point.handle();
+//! tokio::spawn(charge_point.run());
+//! # }
+//! ```
+
+use std::future::Future;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rand::Rng;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::{mpsc, watch};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::handshake::client::Response;
+use tokio_tungstenite::tungstenite::http::{header, HeaderValue};
+use tokio_tungstenite::{tungstenite, WebSocketStream};
+
+use crate::connection::{self, Command, Dispatch};
+pub use crate::connection::{RequestError, OCPP_SUBPROTOCOL};
+use crate::security::{self, AuthorizationKey};
+use crate::{
+    BootNotificationRequest, BootNotificationStatus, ChargePointHandler,
+    HeartbeatRequest, MessageIdGenerator, OCPPCall, OCPPCallError,
+    OCPPCallResult, OCPPRequest, UuidGenerator,
+};
+
+/// Default time to wait for the Central System to respond to a call
+pub const DEFAULT_CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Interval used when the Central System leaves the heartbeat or retry interval to the Charge Point
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Errors returned when connecting to a Central System
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// The WebSocket connection could not be established or failed
+    #[error("websocket error: {0}")]
+    WebSocket(#[from] tungstenite::Error),
+    /// The Central System did not agree to the ocpp1.6 subprotocol
+    #[error("central system did not accept subprotocol ocpp1.6")]
+    Subprotocol,
+    /// The TCP connection or TLS handshake failed
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Exponential backoff with jitter between reconnection attempts
+///
+/// The n-th consecutive delay is drawn uniformly between half and all of `initial * 2^n`, capped at `max`.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+    attempt: u32,
+}
+
+impl Backoff {
+    /// Create a backoff starting at `initial` and never exceeding `max`
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Backoff {
+            initial,
+            max,
+            attempt: 0,
+        }
+    }
+
+    /// Delay before the next attempt
+    pub fn next_delay(&mut self) -> Duration {
+        let ceiling = self
+            .initial
+            .saturating_mul(2u32.saturating_pow(self.attempt))
+            .min(self.max);
+        self.attempt = self.attempt.saturating_add(1);
+
+        ceiling.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+    }
+
+    /// Start again from the initial delay, after a successful connection
+    pub fn reset(&mut self) { self.attempt = 0; }
+}
+
+impl Default for Backoff {
+    /// Starts at 1 second, capped at 5 minutes
+    fn default() -> Self {
+        Backoff::new(Duration::from_secs(1), Duration::from_secs(300))
+    }
+}
+
+/// Charge point connection to a Central System. See the [module documentation](self).
+pub struct ChargePoint<H> {
+    url: String,
+    boot_notification: BootNotificationRequest,
+    handler: H,
+    backoff: Backoff,
+    ids: Arc<dyn MessageIdGenerator>,
+    call_timeout: Duration,
+    commands: mpsc::Sender<Command>,
+    receiver: mpsc::Receiver<Command>,
+    accepted: watch::Sender<bool>,
+    authorization_key: Arc<Mutex<Option<AuthorizationKey>>>,
+    #[cfg(feature = "tls")]
+    tls: Option<Arc<security::rustls::ClientConfig>>,
+}
+
+impl<H: ChargePointHandler + Send> ChargePoint<H> {
+    /// Create a charge point connecting to `url`, e.g. `ws://host/ocpp/CP001`, and identifying itself with `boot_notification`
+    pub fn new(
+        url: impl Into<String>,
+        boot_notification: BootNotificationRequest,
+        handler: H,
+    ) -> Self {
+        let (commands, receiver) = mpsc::channel(16);
+        ChargePoint {
+            url: url.into(),
+            boot_notification,
+            handler,
+            backoff: Backoff::default(),
+            ids: Arc::new(UuidGenerator),
+            call_timeout: DEFAULT_CALL_TIMEOUT,
+            commands,
+            receiver,
+            accepted: watch::Sender::new(false),
+            authorization_key: Arc::default(),
+            #[cfg(feature = "tls")]
+            tls: None,
+        }
+    }
+
+    /// Set the backoff between reconnection attempts. Defaults to [Backoff::default].
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set the time to wait for the Central System to respond to a call. Defaults to [DEFAULT_CALL_TIMEOUT].
+    pub fn call_timeout(mut self, call_timeout: Duration) -> Self {
+        self.call_timeout = call_timeout;
+        self
+    }
+
+    /// Set the generator of unique ids for charge point initiated calls. Defaults to [UuidGenerator].
+    pub fn id_generator(
+        mut self,
+        ids: impl MessageIdGenerator + 'static,
+    ) -> Self {
+        self.ids = Arc::new(ids);
+        self
+    }
+
+    /// Authenticate with HTTP Basic authentication (security profiles 1 and 2), using the charge point id from the URL as user
+    /// name. The key can be changed later with [ChargePointHandle::set_authorization_key].
+    pub fn authorization_key(self, key: AuthorizationKey) -> Self {
+        *lock(&self.authorization_key) = Some(key);
+        self
+    }
+
+    /// Connect to `wss://` URLs over TLS with `config` (security profile 2), which may include a client certificate (security
+    /// profile 3). See [security::client_tls_config].
+    #[cfg(feature = "tls")]
+    pub fn tls(mut self, config: Arc<security::rustls::ClientConfig>) -> Self {
+        self.tls = Some(config);
+        self
+    }
+
+    /// Handle for sending calls to the Central System
+    pub fn handle(&self) -> ChargePointHandle {
+        ChargePointHandle {
+            commands: self.commands.clone(),
+            accepted: self.accepted.subscribe(),
+            authorization_key: self.authorization_key.clone(),
+        }
+    }
+
+    /// Stay connected to the Central System, reconnecting whenever the connection is lost
+    pub async fn run(mut self) {
+        loop {
+            if let Err(e) = self.connect().await {
+                tracing::warn!("OCPP Connection to {} failed: {}", self.url, e);
+            }
+            tokio::time::sleep(self.backoff.next_delay()).await;
+        }
+    }
+
+    /// Connect to the Central System once, returning when the connection closes
+    pub async fn connect(&mut self) -> Result<(), ClientError> {
+        let mut request = self.url.as_str().into_client_request()?;
+        request.headers_mut().insert(
+            header::SEC_WEBSOCKET_PROTOCOL,
+            HeaderValue::from_static(OCPP_SUBPROTOCOL),
+        );
+        let authorization_key = lock(&self.authorization_key).clone();
+        if let Some(key) = authorization_key {
+            let charge_point_id =
+                request.uri().path().rsplit('/').next().unwrap_or_default();
+            let authorization =
+                security::basic_authorization(charge_point_id, &key);
+            request.headers_mut().insert(
+                header::AUTHORIZATION,
+                HeaderValue::try_from(authorization)
+                    .map_err(tungstenite::http::Error::from)
+                    .map_err(tungstenite::Error::from)?,
+            );
+        }
+
+        #[cfg(feature = "tls")]
+        if let Some(config) = self.tls.clone() {
+            let stream = tls_connect(request.uri(), config).await?;
+            let (ws, response) =
+                tokio_tungstenite::client_async(request, stream).await?;
+            return self.session(ws, response).await;
+        }
+        let (ws, response) = tokio_tungstenite::connect_async(request).await?;
+        self.session(ws, response).await
+    }
+
+    /// Exchange messages on an established connection until it closes
+    async fn session<S>(
+        &mut self,
+        ws: WebSocketStream<S>,
+        response: Response,
+    ) -> Result<(), ClientError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        if response.headers().get(header::SEC_WEBSOCKET_PROTOCOL)
+            != Some(&HeaderValue::from_static(OCPP_SUBPROTOCOL))
+        {
+            return Err(ClientError::Subprotocol);
+        }
+        tracing::info!("OCPP Connected to {}", self.url);
+
+        // Calls queued while disconnected must not precede the BootNotification.req
+        fail_queued_calls(&mut self.receiver);
+        let result = tokio::select! {
+            result = connection::run(
+                ws,
+                &mut self.receiver,
+                self.ids.clone(),
+                self.call_timeout,
+                ChargePointDispatch(&mut self.handler),
+            ) => result.map_err(ClientError::from),
+            () = keep_alive(
+                &self.commands,
+                &self.boot_notification,
+                &self.accepted,
+                &mut self.backoff,
+            ) => Ok(()),
+        };
+
+        self.accepted.send_replace(false);
+        fail_queued_calls(&mut self.receiver);
+        tracing::info!("OCPP Disconnected from {}", self.url);
+        result
+    }
+}
+
+/// Handle for sending charge point initiated calls to the Central System. Cheap to clone.
+#[derive(Clone)]
+pub struct ChargePointHandle {
+    commands: mpsc::Sender<Command>,
+    accepted: watch::Receiver<bool>,
+    authorization_key: Arc<Mutex<Option<AuthorizationKey>>>,
+}
+
+impl ChargePointHandle {
+    /// Whether the charge point is connected and accepted by the Central System
+    pub fn is_accepted(&self) -> bool { *self.accepted.borrow() }
+
+    /// Replace the HTTP Basic authentication key, e.g. after the [AUTHORIZATION_KEY](security::AUTHORIZATION_KEY)
+    /// configuration key was changed by the Central System. Takes effect on the next connection.
+    pub fn set_authorization_key(&self, key: AuthorizationKey) {
+        *lock(&self.authorization_key) = Some(key);
+    }
+
+    /// Send a call to the Central System and await its response. Waits until the charge point is connected and its
+    /// BootNotification.req has been accepted. Fails with [RequestError::ConnectionClosed] if the connection is lost before
+    /// the response is received, even if the call was not sent yet.
+    pub async fn call<R: OCPPRequest>(
+        &self,
+        req: R,
+    ) -> Result<R::Response, RequestError> {
+        if !R::ACTION.is_sent_by_charge_point() {
+            return Err(RequestError::WrongDirection(R::ACTION));
+        }
+
+        let mut accepted = self.accepted.clone();
+        accepted
+            .wait_for(|accepted| *accepted)
+            .await
+            .map_err(|_| RequestError::ConnectionClosed)?;
+        connection::send_call(&self.commands, req).await
+    }
+}
+
+/// Dispatches calls from the Central System to the charge point handler
+struct ChargePointDispatch<'a, H>(&'a mut H);
+
+impl<H: ChargePointHandler + Send> Dispatch for ChargePointDispatch<'_, H> {
+    fn dispatch(
+        &mut self,
+        call: OCPPCall,
+    ) -> impl Future<Output = Result<OCPPCallResult, OCPPCallError>> + Send + 'static
+    {
+        let result = if call.payload.action().is_sent_by_central_system() {
+            self.0.build_response(call)
+        } else {
+            Err(connection::not_supported(&call))
+        };
+        std::future::ready(result)
+    }
+}
+
+/// Send BootNotification.req until accepted, then Heartbeat.req at the accepted interval. Returns when the connection closes.
+async fn keep_alive(
+    commands: &mpsc::Sender<Command>,
+    boot_notification: &BootNotificationRequest,
+    accepted: &watch::Sender<bool>,
+    backoff: &mut Backoff,
+) {
+    let interval = loop {
+        match connection::send_call(commands, boot_notification.clone()).await {
+            Ok(res) if res.status == BootNotificationStatus::Accepted => {
+                break interval(res.interval);
+            },
+            Ok(res) => {
+                tracing::info!("OCPP Boot notification {}", res.status);
+                tokio::time::sleep(interval(res.interval)).await;
+            },
+            Err(RequestError::ConnectionClosed) => return,
+            Err(e) => {
+                tracing::warn!("OCPP Boot notification failed: {}", e);
+                tokio::time::sleep(backoff.next_delay()).await;
+            },
+        }
+    };
+    backoff.reset();
+    accepted.send_replace(true);
+
+    loop {
+        tokio::time::sleep(interval).await;
+        match connection::send_call(commands, HeartbeatRequest {}).await {
+            Ok(_) => {},
+            Err(RequestError::ConnectionClosed) => return,
+            Err(e) => tracing::warn!("OCPP Heartbeat failed: {}", e),
+        }
+    }
+}
+
+/// Fail the calls still queued for a connection which closed, instead of sending them on the next one
+fn fail_queued_calls(commands: &mut mpsc::Receiver<Command>) {
+    while let Ok(Command::Call { reply, .. }) = commands.try_recv() {
+        let _ = reply.send(Err(RequestError::ConnectionClosed));
+    }
+}
+
+/// Open a TCP connection to the host of `uri` and perform the TLS handshake
+#[cfg(feature = "tls")]
+async fn tls_connect(
+    uri: &tungstenite::http::Uri,
+    config: Arc<security::rustls::ClientConfig>,
+) -> io::Result<tokio_rustls::client::TlsStream<tokio::net::TcpStream>> {
+    use security::rustls::pki_types::ServerName;
+
+    let host = uri.host().unwrap_or_default();
+    let port = uri.port_u16().unwrap_or(443);
+    // IPv6 literals are bracketed in URLs
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let server_name = ServerName::try_from(String::from(host))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let stream = tokio::net::TcpStream::connect((host, port)).await?;
+    tokio_rustls::TlsConnector::from(config).connect(server_name, stream).await
+}
+
+fn lock(
+    key: &Mutex<Option<AuthorizationKey>>,
+) -> std::sync::MutexGuard<'_, Option<AuthorizationKey>> {
+    key.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn interval(seconds: u32) -> Duration {
+    match seconds {
+        0 => DEFAULT_INTERVAL,
+        seconds => Duration::from_secs(seconds.into()),
+    }
+}
@@ -0,0 +1,42 @@
+//! Certificate related types introduced by the OCPP 1.6 Security Whitepaper
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+/// Identifies a certificate by the hashes of its issuer name and key and its serial number, as used in
+/// [DeleteCertificate.req](crate::server_init::delete_certificate) and [GetInstalledCertificateIds.conf](crate::server_init::get_installed_certificate_ids).
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CertificateHashData {
+    /// Required. Used algorithms for the hashes provided.
+    pub hash_algorithm: HashAlgorithm,
+    /// Required. The hash of the issuer’s distinguished name (DN), that must be calculated over the DER encoding of the issuer’s name field in the certificate being checked.
+    pub issuer_name_hash: String,
+    /// Required. The hash of the DER encoded public key: the value (excluding tag and length) of the subject public key field in the issuer’s certificate.
+    pub issuer_key_hash: String,
+    /// Required. The serial number as a hexadecimal string without leading zeroes (and without the prefix 0x).
+    pub serial_number: String,
+}
+
+/// Hash algorithm used in [CertificateHashData].
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+pub enum HashAlgorithm {
+    /// SHA-256 hash algorithm.
+    #[serde(rename = "SHA256")]
+    Sha256,
+    /// SHA-384 hash algorithm.
+    #[serde(rename = "SHA384")]
+    Sha384,
+    /// SHA-512 hash algorithm.
+    #[serde(rename = "SHA512")]
+    Sha512,
+}
+
+/// Type of root certificate, as used in [InstallCertificate.req](crate::server_init::install_certificate) and
+/// [GetInstalledCertificateIds.req](crate::server_init::get_installed_certificate_ids).
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+pub enum CertificateUse {
+    /// Root certificate, used by the CA to sign the Central System and Charge Point certificate.
+    CentralSystemRootCertificate,
+    /// Root certificate for verification of the Manufacturer certificate.
+    ManufacturerRootCertificate,
+}
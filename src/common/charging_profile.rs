@@ -19,10 +19,15 @@
 //!             Limit                   f32
 //!             NumberPhases            Option<u32>
 //! ```
+//!
+//! The JSON schema only checks the shape of a profile. [ChargingProfile::check] also checks the rules of the specification
+//! between its fields, such as the order of the schedule periods, and [ChargingProfileBuilder::try_build] builds a profile
+//! only if it passes.
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use strum_macros::Display;
+use thiserror::Error;
 
 /// A ChargingProfile consists of a ChargingSchedule, describing the amount of power or current that can be delivered per time interval.
 #[skip_serializing_none]
@@ -49,6 +54,59 @@ pub struct ChargingProfile {
     pub charging_schedule: ChargingSchedule,
 }
 
+/// Rule of the specification broken by a [ChargingProfile], as found by [ChargingProfile::check]
+#[derive(Debug, Error, PartialEq, Clone)]
+pub enum ChargingProfileError {
+    /// The charging schedule has no periods
+    #[error("charging schedule has no periods")]
+    NoPeriods,
+    /// The first period does not start at 0
+    #[error("first charging schedule period starts at {0} instead of 0")]
+    FirstPeriodNotAtZero(u32),
+    /// The start periods are not strictly ascending
+    #[error("charging schedule period starting at {start_period} follows the period starting at {previous}")]
+    PeriodsNotAscending {
+        /// Start of the previous period
+        previous: u32,
+        /// Start of the offending period
+        start_period: u32,
+    },
+    /// A limit has more than one digit fraction
+    #[error("limit {limit} of the period starting at {start_period} is not a multiple of 0.1")]
+    LimitNotMultipleOfTenth {
+        /// Start of the offending period
+        start_period: u32,
+        /// Offending limit
+        limit: f32,
+    },
+    /// The minimum charging rate has more than one digit fraction
+    #[error("minimum charging rate {0} is not a multiple of 0.1")]
+    MinChargingRateNotMultipleOfTenth(f32),
+    /// A Recurring profile has no recurrency kind
+    #[error("recurring charging profile has no recurrency kind")]
+    MissingRecurrencyKind,
+    /// A Recurring profile has no start schedule
+    #[error("recurring charging profile has no start schedule")]
+    MissingStartSchedule,
+    /// The schedule of a Recurring profile lasts longer than its recurrence
+    #[error("charging schedule duration of {duration} s exceeds the {recurrency_kind} recurrence")]
+    DurationExceedsRecurrence {
+        /// Duration of the schedule in seconds
+        duration: u32,
+        /// Recurrence of the profile
+        recurrency_kind: RecurrencyKind,
+    },
+    /// A transaction id is set on a profile which is not a TxProfile
+    #[error("transaction id set on a {0}")]
+    TransactionIdWithoutTxProfile(ChargingProfilePurpose),
+    /// A TxProfile in a SetChargingProfile.req does not name its transaction
+    #[error("TxProfile without transaction id")]
+    MissingTransactionId,
+    /// A TxProfile in a SetChargingProfile.req is set on connector 0
+    #[error("TxProfile set on connector 0")]
+    TxProfileOnConnectorZero,
+}
+
 /// Charging schedule structure defines a list of charging periods, as used in: [GetCompositeSchedule.conf](crate::server_init::get_composite_schedule) and [ChargingProfile]).
 #[skip_serializing_none]
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -123,13 +181,17 @@ pub enum ChargingRateUnit {
 }
 
 /// Typestate value for Id
+#[derive(Debug, Clone)]
 pub struct Id(u32);
 /// Typestate value for missing Id
+#[derive(Debug, Clone)]
 pub struct NoId;
 
 /// Typestate value for Level
+#[derive(Debug, Clone)]
 pub struct Level(u32);
 /// Typestate value for missing Level
+#[derive(Debug, Clone)]
 pub struct NoLevel;
 
 #[derive(Debug, Clone)]
@@ -162,6 +224,79 @@ impl ChargingProfile {
     ) -> ChargingProfileBuilder<NoId, NoLevel> {
         ChargingProfileBuilder::new(charging_rate_unit)
     }
+
+    /// Check the rules of the specification which the JSON schema cannot express, returning the first rule broken
+    pub fn check(&self) -> Result<(), ChargingProfileError> {
+        let schedule = &self.charging_schedule;
+        let periods = &schedule.charging_schedule_period;
+        let first = periods.first().ok_or(ChargingProfileError::NoPeriods)?;
+        if first.start_period != 0 {
+            return Err(ChargingProfileError::FirstPeriodNotAtZero(
+                first.start_period,
+            ));
+        }
+        for pair in periods.windows(2) {
+            if pair[1].start_period <= pair[0].start_period {
+                return Err(ChargingProfileError::PeriodsNotAscending {
+                    previous: pair[0].start_period,
+                    start_period: pair[1].start_period,
+                });
+            }
+        }
+        if let Some(period) =
+            periods.iter().find(|period| !is_multiple_of_tenth(period.limit))
+        {
+            return Err(ChargingProfileError::LimitNotMultipleOfTenth {
+                start_period: period.start_period,
+                limit: period.limit,
+            });
+        }
+        if let Some(rate) = schedule
+            .min_charging_rate
+            .filter(|rate| !is_multiple_of_tenth(*rate))
+        {
+            return Err(
+                ChargingProfileError::MinChargingRateNotMultipleOfTenth(rate),
+            );
+        }
+
+        if self.charging_profile_kind == ChargingProfileKind::Recurring {
+            let recurrency_kind = self
+                .recurrency_kind
+                .as_ref()
+                .ok_or(ChargingProfileError::MissingRecurrencyKind)?;
+            if schedule.start_schedule.is_none() {
+                return Err(ChargingProfileError::MissingStartSchedule);
+            }
+            let recurrence = match recurrency_kind {
+                RecurrencyKind::Daily => 24 * 3600,
+                RecurrencyKind::Weekly => 7 * 24 * 3600,
+            };
+            if let Some(duration) =
+                schedule.duration.filter(|duration| *duration > recurrence)
+            {
+                return Err(ChargingProfileError::DurationExceedsRecurrence {
+                    duration,
+                    recurrency_kind: recurrency_kind.clone(),
+                });
+            }
+        }
+
+        if self.transaction_id.is_some()
+            && self.charging_profile_purpose
+                != ChargingProfilePurpose::TxProfile
+        {
+            return Err(ChargingProfileError::TransactionIdWithoutTxProfile(
+                self.charging_profile_purpose.clone(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Whether a limit has at most one digit fraction
+fn is_multiple_of_tenth(value: f32) -> bool {
+    (value * 10.0).round() / 10.0 == value
 }
 
 impl ChargingProfileBuilder<NoId, NoLevel> {
@@ -381,4 +516,11 @@ impl ChargingProfileBuilder<Id, Level> {
             charging_schedule: self.charging_schedule,
         }
     }
+
+    /// Build ChargingProfile from existing builder struct, failing if it breaks a rule checked by [ChargingProfile::check]
+    pub fn try_build(self) -> Result<ChargingProfile, ChargingProfileError> {
+        let profile = self.build();
+        profile.check()?;
+        Ok(profile)
+    }
 }
@@ -1,10 +1,12 @@
 //! A collection of common data structures.
 
+pub mod certificate;
 pub mod charging_profile;
 pub mod common_types;
 pub mod data_transfer;
 pub mod meter_value;
 
+pub use certificate::*;
 pub use charging_profile::*;
 pub use common_types::*;
 pub use data_transfer::*;
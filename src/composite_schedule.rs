@@ -0,0 +1,374 @@
+//! Calculation of the Composite Charging Schedule of a connector
+//!
+//! [CompositeScheduleCalculator] merges the charging profiles installed for a connector into the schedule reported in a
+//! GetCompositeSchedule.conf, so that a Charge Point and a Central System holding the same profiles arrive at the same answer.
+//!
+//! At any point in time, the profile with the highest stack level applies within each purpose. A TxProfile overrides the
+//! TxDefaultProfile, and the resulting limit is capped by the ChargePointMaxProfile. Profiles only apply within their
+//! `valid_from`/`valid_to` window and the duration of their schedule:
+//!
+//! - Absolute schedules start at their `start_schedule`
+//! - Relative schedules start with the transaction, or at the start of the composite schedule without one
+//! - Recurring schedules restart every day or week after their `start_schedule`
+//!
+//! The caller passes the profiles which apply to the connector: the ChargePointMaxProfiles of the Charge Point, and the
+//! TxDefaultProfiles and TxProfiles of the connector and its transaction. Limits in another unit than the requested one are
+//! converted using the [voltage](CompositeScheduleCalculator::voltage) and the number of phases of the period, 3 if absent.
+//! Where no profile applies, the [maximum current](CompositeScheduleCalculator::max_current) of the connector is reported.
+//!
+//! # Example
+//! ```
+//! # fn ocpp_composite_schedule_example() {
+//! use ocpp::*;
+//!
+//! let start = chrono::Utc::now();
+//! let max = ChargingProfile::builder(ChargingRateUnit::A)
+//!     .purpose(ChargingProfilePurpose::ChargePointMaxProfile)
+//!     .kind(ChargingProfileKind::Absolute)
+//!     .schedule_start(start)
+//!     .id(1)
+//!     .stack_level(0)
+//!     .add_period(0, 16.0, None)
+//!     .build();
+//! let tx = ChargingProfile::builder(ChargingRateUnit::A)
+//!     .id(2)
+//!     .stack_level(0)
+//!     .add_period(0, 32.0, None)
+//!     .add_period(600, 10.0, None)
+//!     .build();
+//!
+//! let schedule = composite_schedule(&[max, tx], start, 3600, None);
+//! let limits: Vec<(u32, f32)> = schedule
+//!     .charging_schedule_period
+//!     .iter()
+//!     .map(|period| (period.start_period, period.limit))
+//!     .collect();
+//! assert_eq!(limits, vec![(0, 16.0), (600, 10.0)]);
+//! # }
+//! ```
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    ChargingProfile, ChargingProfileKind, ChargingProfilePurpose,
+    ChargingRateUnit, ChargingSchedule, ChargingSchedulePeriod,
+    GetCompositeScheduleRequest, GetCompositeScheduleResponse, RecurrencyKind,
+    SimpleStatus,
+};
+
+/// Voltage used to convert between A and W unless set with [CompositeScheduleCalculator::voltage]
+pub const DEFAULT_VOLTAGE: f32 = 230.0;
+
+/// Current per phase in A reported where no profile applies unless set with [CompositeScheduleCalculator::max_current]
+pub const DEFAULT_MAX_CURRENT: f32 = 32.0;
+
+/// Number of phases assumed when a period does not specify it
+const DEFAULT_NUMBER_PHASES: u32 = 3;
+
+/// Merges charging profiles into a Composite Charging Schedule. See the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct CompositeScheduleCalculator {
+    transaction_start: Option<DateTime<Utc>>,
+    voltage: f32,
+    max_current: f32,
+}
+
+impl Default for CompositeScheduleCalculator {
+    /// No transaction, [DEFAULT_VOLTAGE] and [DEFAULT_MAX_CURRENT]
+    fn default() -> Self {
+        CompositeScheduleCalculator {
+            transaction_start: None,
+            voltage: DEFAULT_VOLTAGE,
+            max_current: DEFAULT_MAX_CURRENT,
+        }
+    }
+}
+
+impl CompositeScheduleCalculator {
+    /// Start of the transaction on the connector, at which Relative schedules start
+    pub fn transaction_start(
+        mut self,
+        transaction_start: DateTime<Utc>,
+    ) -> Self {
+        self.transaction_start = Some(transaction_start);
+        self
+    }
+
+    /// Voltage of the grid connection, used to convert between A and W
+    pub fn voltage(mut self, voltage: f32) -> Self {
+        self.voltage = voltage;
+        self
+    }
+
+    /// Maximum current per phase of the connector in A, reported where no profile applies
+    pub fn max_current(mut self, max_current: f32) -> Self {
+        self.max_current = max_current;
+        self
+    }
+
+    /// Composite schedule of `profiles` from `start` for `duration` seconds, in `charging_rate_unit` or else in the unit of
+    /// the first profile
+    pub fn calculate(
+        &self,
+        profiles: &[ChargingProfile],
+        start: DateTime<Utc>,
+        duration: u32,
+        charging_rate_unit: Option<ChargingRateUnit>,
+    ) -> ChargingSchedule {
+        let unit = charging_rate_unit
+            .or_else(|| {
+                profiles
+                    .first()
+                    .map(|p| p.charging_schedule.charging_rate_unit.clone())
+            })
+            .unwrap_or(ChargingRateUnit::A);
+        let profiles: Vec<Placed> = profiles
+            .iter()
+            .filter_map(|profile| self.place(profile, start))
+            .collect();
+
+        let mut breakpoints: Vec<i64> = profiles
+            .iter()
+            .flat_map(|profile| profile.breakpoints(i64::from(duration)))
+            .filter(|t| (1..i64::from(duration)).contains(t))
+            .collect();
+        breakpoints.push(0);
+        breakpoints.sort_unstable();
+        breakpoints.dedup();
+
+        let mut periods: Vec<ChargingSchedulePeriod> = Vec::new();
+        for t in breakpoints {
+            let period = self.limit_at(&profiles, t, &unit);
+            let unchanged = periods.last().is_some_and(|last| {
+                last.limit == period.limit
+                    && last.number_phases == period.number_phases
+            });
+            if !unchanged {
+                periods.push(ChargingSchedulePeriod {
+                    start_period: t as u32,
+                    ..period
+                });
+            }
+        }
+
+        ChargingSchedule {
+            duration: Some(duration),
+            start_schedule: Some(start),
+            charging_rate_unit: unit,
+            charging_schedule_period: periods,
+            min_charging_rate: None,
+        }
+    }
+
+    /// Answer a GetCompositeSchedule.req received at `now` from the profiles applying to its connector
+    pub fn get_composite_schedule(
+        &self,
+        req: &GetCompositeScheduleRequest,
+        profiles: &[ChargingProfile],
+        now: DateTime<Utc>,
+    ) -> GetCompositeScheduleResponse {
+        GetCompositeScheduleResponse {
+            status: SimpleStatus::Accepted,
+            connector_id: Some(req.connector_id),
+            schedule_start: Some(now),
+            charging_schedule: Some(self.calculate(
+                profiles,
+                now,
+                req.duration,
+                req.charging_rate_unit.clone(),
+            )),
+        }
+    }
+
+    /// Anchor a profile on the timeline of the composite schedule
+    fn place<'a>(
+        &self,
+        profile: &'a ChargingProfile,
+        start: DateTime<Utc>,
+    ) -> Option<Placed<'a>> {
+        let schedule_start = match profile.charging_profile_kind {
+            ChargingProfileKind::Relative => {
+                self.transaction_start.unwrap_or(start)
+            },
+            ChargingProfileKind::Absolute | ChargingProfileKind::Recurring => {
+                let Some(schedule_start) = profile
+                    .charging_schedule
+                    .start_schedule
+                    .or(profile.valid_from)
+                else {
+                    tracing::warn!(
+                        "OCPP Charging profile {} has no start schedule",
+                        profile.charging_profile_id
+                    );
+                    return None;
+                };
+                schedule_start
+            },
+        };
+        let offset = |time: DateTime<Utc>| (time - start).num_seconds();
+        Some(Placed {
+            profile,
+            anchor: offset(schedule_start),
+            valid_from: profile.valid_from.map(offset),
+            valid_to: profile.valid_to.map(offset),
+            recurrence: match (
+                &profile.charging_profile_kind,
+                &profile.recurrency_kind,
+            ) {
+                (
+                    ChargingProfileKind::Recurring,
+                    Some(RecurrencyKind::Weekly),
+                ) => Some(7 * 24 * 3600),
+                (ChargingProfileKind::Recurring, _) => Some(24 * 3600),
+                _ => None,
+            },
+        })
+    }
+
+    /// Limit at `t` seconds into the composite schedule, with the number of phases of the period imposing it
+    fn limit_at(
+        &self,
+        profiles: &[Placed],
+        t: i64,
+        unit: &ChargingRateUnit,
+    ) -> ChargingSchedulePeriod {
+        let winner = |purpose: ChargingProfilePurpose| {
+            profiles
+                .iter()
+                .filter(|p| p.profile.charging_profile_purpose == purpose)
+                .filter_map(|p| Some((p.profile, p.period_at(t)?)))
+                .max_by_key(|(profile, _)| profile.stack_level)
+        };
+        let max = winner(ChargingProfilePurpose::ChargePointMaxProfile);
+        let tx = winner(ChargingProfilePurpose::TxProfile)
+            .or_else(|| winner(ChargingProfilePurpose::TxDefaultProfile));
+
+        [max, tx]
+            .into_iter()
+            .flatten()
+            .map(|(profile, period)| ChargingSchedulePeriod {
+                start_period: 0,
+                limit: self.convert(
+                    period.limit,
+                    &profile.charging_schedule.charging_rate_unit,
+                    unit,
+                    period.number_phases,
+                ),
+                number_phases: period.number_phases,
+            })
+            .min_by(|a, b| a.limit.total_cmp(&b.limit))
+            .unwrap_or_else(|| ChargingSchedulePeriod {
+                start_period: 0,
+                limit: self.convert(
+                    self.max_current,
+                    &ChargingRateUnit::A,
+                    unit,
+                    None,
+                ),
+                number_phases: None,
+            })
+    }
+
+    /// Convert a limit between A per phase and W, rounding down to the one digit fraction allowed in a schedule
+    fn convert(
+        &self,
+        limit: f32,
+        from: &ChargingRateUnit,
+        to: &ChargingRateUnit,
+        number_phases: Option<u32>,
+    ) -> f32 {
+        let watts_per_amp = self.voltage
+            * number_phases.unwrap_or(DEFAULT_NUMBER_PHASES) as f32;
+        let converted = match (from, to) {
+            (ChargingRateUnit::A, ChargingRateUnit::W) => limit * watts_per_amp,
+            (ChargingRateUnit::W, ChargingRateUnit::A) => limit / watts_per_amp,
+            _ => return limit,
+        };
+        (converted * 10.0).floor() / 10.0
+    }
+}
+
+/// Composite schedule of `profiles` from `start` for `duration` seconds, without a transaction and with the default voltage
+/// and maximum current. See [CompositeScheduleCalculator].
+pub fn composite_schedule(
+    profiles: &[ChargingProfile],
+    start: DateTime<Utc>,
+    duration: u32,
+    charging_rate_unit: Option<ChargingRateUnit>,
+) -> ChargingSchedule {
+    CompositeScheduleCalculator::default().calculate(
+        profiles,
+        start,
+        duration,
+        charging_rate_unit,
+    )
+}
+
+/// A profile with its times in seconds from the start of the composite schedule
+struct Placed<'a> {
+    profile: &'a ChargingProfile,
+    /// Start of the schedule, or of its first recurrence
+    anchor: i64,
+    valid_from: Option<i64>,
+    valid_to: Option<i64>,
+    /// Seconds between recurrences
+    recurrence: Option<i64>,
+}
+
+impl Placed<'_> {
+    /// Period of the schedule in force at `t`, if the profile applies. A recurring profile does not apply before its
+    /// first recurrence.
+    fn period_at(&self, t: i64) -> Option<&ChargingSchedulePeriod> {
+        if self.valid_from.is_some_and(|from| t < from)
+            || self.valid_to.is_some_and(|to| t >= to)
+            || (self.recurrence.is_some() && t < self.anchor)
+        {
+            return None;
+        }
+        let schedule_start = match self.recurrence {
+            Some(recurrence) => {
+                self.anchor
+                    + (t - self.anchor).div_euclid(recurrence) * recurrence
+            },
+            None => self.anchor,
+        };
+        let elapsed = t - schedule_start;
+        let schedule = &self.profile.charging_schedule;
+        if elapsed < 0
+            || schedule.duration.is_some_and(|d| elapsed >= i64::from(d))
+        {
+            return None;
+        }
+        schedule
+            .charging_schedule_period
+            .iter()
+            .filter(|period| i64::from(period.start_period) <= elapsed)
+            .max_by_key(|period| period.start_period)
+    }
+
+    /// Times within `0..end` at which the limit of the profile may change
+    fn breakpoints(&self, end: i64) -> Vec<i64> {
+        let schedule_starts: Vec<i64> = match self.recurrence {
+            Some(recurrence) => {
+                let first = (0 - self.anchor).div_euclid(recurrence).max(0);
+                let last = (end - self.anchor).div_euclid(recurrence);
+                (first..=last).map(|n| self.anchor + n * recurrence).collect()
+            },
+            None => vec![self.anchor],
+        };
+        let schedule = &self.profile.charging_schedule;
+        let mut breakpoints: Vec<i64> =
+            self.valid_from.into_iter().chain(self.valid_to).collect();
+        for schedule_start in schedule_starts {
+            breakpoints.extend(
+                schedule.charging_schedule_period.iter().map(|period| {
+                    schedule_start + i64::from(period.start_period)
+                }),
+            );
+            breakpoints.extend(
+                schedule.duration.map(|d| schedule_start + i64::from(d)),
+            );
+        }
+        breakpoints
+    }
+}
@@ -0,0 +1,733 @@
+//! Standard configuration keys of OCPP 1.6 and a store answering GetConfiguration.req and ChangeConfiguration.req
+//!
+//! [ConfigurationKey] names every standard configuration key of OCPP 1.6 and of the security extension. Each key knows the
+//! [FeatureProfile] it belongs to, its [Accessibility] and its [ValueType], and parses the string carried in a
+//! ChangeConfiguration.req into a [ConfigurationValue].
+//!
+//! [ConfigurationStore] holds the keys supported by a Charge Point. It answers a GetConfiguration.req with the requested keys,
+//! reporting those it does not support as unknown and refusing requests for more keys than GetConfigurationMaxKeys, and
+//! applies a ChangeConfiguration.req following the rules of the ChangeConfiguration.conf:
+//!
+//! - NotSupported if the key is not a standard key or is not supported by the Charge Point
+//! - Rejected if the key is read-only, the value does not parse, a list is longer than its `MaxLength` key allows, or the
+//!   SecurityProfile would be lowered
+//! - RebootRequired if the key was marked with [ConfigurationStore::reboot_required]
+//! - Accepted otherwise
+//!
+//! # Example
+//! ```
+//! # fn ocpp_configuration_example() -> Result<(), Box<dyn std::error::Error>> {
+//! use ocpp::*;
+//!
+//! let mut store = ConfigurationStore::default();
+//! store.insert(ConfigurationKey::HeartbeatInterval, ConfigurationValue::Integer(300))?;
+//! store.insert(
+//!     ConfigurationKey::MeterValuesSampledData,
+//!     ConfigurationKey::MeterValuesSampledData.parse("Energy.Active.Import.Register")?,
+//! )?;
+//!
+//! let req = ChangeConfigurationRequest {
+//!     key: String::from("MeterValuesSampledData"),
+//!     value: String::from("Energy.Active.Import.Register,Power.Active.Import"),
+//! };
+//! assert_eq!(store.change_configuration(&req).status, ChangeConfigurationStatus::Accepted);
+//!
+//! let req = GetConfigurationRequest {
+//!     key: Some(vec![String::from("HeartbeatInterval"), String::from("Vendor")]),
+//! };
+//! let res = store.get_configuration(&req)?;
+//! assert_eq!(res.unknown_key, Some(vec![String::from("Vendor")]));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use strum_macros::{Display, EnumIter, EnumString};
+use thiserror::Error;
+
+use crate::{
+    ChangeConfigurationRequest, ChangeConfigurationResponse,
+    ChangeConfigurationStatus, GetConfigurationRequest,
+    GetConfigurationResponse, KeyValue, OCPPCallErrorCode, SampledMeasurand,
+};
+
+/// Minimum length of the value of AuthorizationKey
+pub const MIN_AUTHORIZATION_KEY_LENGTH: usize = 16;
+
+/// Maximum length of the value of AuthorizationKey
+pub const MAX_AUTHORIZATION_KEY_LENGTH: usize = 40;
+
+/// Whether `key` is a valid value of AuthorizationKey: 16 to 40 printable ASCII characters
+pub fn is_valid_authorization_key(key: &str) -> bool {
+    (MIN_AUTHORIZATION_KEY_LENGTH..=MAX_AUTHORIZATION_KEY_LENGTH)
+        .contains(&key.len())
+        && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Errors returned when parsing or storing a configuration value
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ConfigurationError {
+    /// The value does not follow the parsing rules of the key
+    #[error("invalid value {value:?} for {key}")]
+    InvalidValue {
+        /// Key being set
+        key: ConfigurationKey,
+        /// Rejected value
+        value: String,
+    },
+    /// The value is of another type than the key
+    #[error("{key} expects a value of type {expected}")]
+    WrongType {
+        /// Key being set
+        key: ConfigurationKey,
+        /// Type of the key
+        expected: ValueType,
+    },
+    /// The list has more items than the `MaxLength` key of the list allows
+    #[error("{key} allows at most {max_length} items")]
+    TooManyItems {
+        /// Key being set
+        key: ConfigurationKey,
+        /// Value of the `MaxLength` key
+        max_length: u32,
+    },
+}
+
+/// Feature profile to which a configuration key belongs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+pub enum FeatureProfile {
+    /// Basic Charge Point functionality
+    Core,
+    /// Firmware update and diagnostic log file download
+    FirmwareManagement,
+    /// Local Authorization List management
+    LocalAuthListManagement,
+    /// Reservations
+    Reservation,
+    /// Smart charging
+    SmartCharging,
+    /// TriggerMessage
+    RemoteTrigger,
+    /// Keys of the security extension, which is not reported in SupportedFeatureProfiles
+    Security,
+}
+
+/// Whether a configuration key may be read and changed by the Central System
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Accessibility {
+    /// Reported by GetConfiguration.req, but cannot be changed
+    ReadOnly,
+    /// Reported by GetConfiguration.req and changed by ChangeConfiguration.req
+    ReadWrite,
+    /// Changed by ChangeConfiguration.req, but its value is never reported
+    WriteOnly,
+}
+
+/// Type of the value of a configuration key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum ValueType {
+    /// `true` or `false`
+    Boolean,
+    /// Non-negative integer
+    Integer,
+    /// Free text
+    String,
+    /// Comma separated list of [SampledMeasurand]s
+    MeasurandList,
+    /// Comma separated list of connector phase rotations, e.g. `0.RST, 1.RTS`
+    PhaseRotationList,
+    /// Comma separated list of [FeatureProfile]s, other than [FeatureProfile::Security]
+    ProfileList,
+    /// Comma separated list of `Current` and `Power`
+    ChargingRateUnitList,
+}
+
+/// Standard configuration key of OCPP 1.6 or of the security extension. Keys are parsed case-insensitively.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Display,
+    EnumString,
+    EnumIter,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum ConfigurationKey {
+    // Core
+    /// Whether unknown identifiers may start a transaction while offline
+    AllowOfflineTxForUnknownId,
+    /// Whether the Authorization Cache is enabled
+    AuthorizationCacheEnabled,
+    /// Whether a RemoteStartTransaction.req is authorized like a local action
+    AuthorizeRemoteTxRequests,
+    /// Number of times to blink the lights when signalling
+    BlinkRepeat,
+    /// Interval in seconds of clock-aligned meter data, 0 disabling it
+    ClockAlignedDataInterval,
+    /// Seconds to wait for the EV to be connected after authorization
+    ConnectionTimeOut,
+    /// Phase rotation of each connector relative to the grid connection
+    ConnectorPhaseRotation,
+    /// Maximum number of items in ConnectorPhaseRotation
+    ConnectorPhaseRotationMaxLength,
+    /// Maximum number of keys in a GetConfiguration.req
+    GetConfigurationMaxKeys,
+    /// Interval in seconds between Heartbeat.req
+    HeartbeatInterval,
+    /// Percentage of maximum intensity of the lights
+    LightIntensity,
+    /// Whether locally authorized identifiers may start a transaction while offline
+    LocalAuthorizeOffline,
+    /// Whether locally authorized identifiers may start a transaction without waiting for Authorize.conf
+    LocalPreAuthorize,
+    /// Energy in Wh delivered after the identifier of a transaction turned out invalid
+    MaxEnergyOnInvalidId,
+    /// Measurands of clock-aligned MeterValues.req
+    MeterValuesAlignedData,
+    /// Maximum number of items in MeterValuesAlignedData
+    MeterValuesAlignedDataMaxLength,
+    /// Measurands of sampled MeterValues.req during a transaction
+    MeterValuesSampledData,
+    /// Maximum number of items in MeterValuesSampledData
+    MeterValuesSampledDataMaxLength,
+    /// Interval in seconds between sampled meter values, 0 disabling them
+    MeterValueSampleInterval,
+    /// Seconds a status must persist before a StatusNotification.req is sent
+    MinimumStatusDuration,
+    /// Number of physical connectors
+    NumberOfConnectors,
+    /// Number of times to retry an unsuccessful reset
+    ResetRetries,
+    /// Whether the transaction stops when the cable is unplugged from the EV
+    StopTransactionOnEVSideDisconnect,
+    /// Whether the transaction stops when its identifier turns out invalid
+    StopTransactionOnInvalidId,
+    /// Clock-aligned measurands in the transaction data of StopTransaction.req
+    StopTxnAlignedData,
+    /// Maximum number of items in StopTxnAlignedData
+    StopTxnAlignedDataMaxLength,
+    /// Sampled measurands in the transaction data of StopTransaction.req
+    StopTxnSampledData,
+    /// Maximum number of items in StopTxnSampledData
+    StopTxnSampledDataMaxLength,
+    /// Feature profiles supported by the Charge Point
+    SupportedFeatureProfiles,
+    /// Maximum number of items in SupportedFeatureProfiles
+    SupportedFeatureProfilesMaxLength,
+    /// Number of attempts to deliver a transaction-related message
+    TransactionMessageAttempts,
+    /// Seconds between attempts to deliver a transaction-related message
+    TransactionMessageRetryInterval,
+    /// Whether the connector unlocks when the cable is unplugged from the EV
+    UnlockConnectorOnEVSideDisconnect,
+    /// Interval in seconds between WebSocket pings, 0 disabling them
+    WebSocketPingInterval,
+    // Local Auth List Management
+    /// Whether the Local Authorization List is enabled
+    LocalAuthListEnabled,
+    /// Maximum number of identifiers in the Local Authorization List
+    LocalAuthListMaxLength,
+    /// Maximum number of identifiers in a SendLocalList.req
+    SendLocalListMaxLength,
+    // Reservation
+    /// Whether connector 0 can be reserved
+    ReserveConnectorZeroSupported,
+    // Smart Charging
+    /// Maximum stack level of a charging profile
+    ChargeProfileMaxStackLevel,
+    /// Charging rate units supported in charging schedules
+    ChargingScheduleAllowedChargingRateUnit,
+    /// Maximum number of periods in a charging schedule
+    ChargingScheduleMaxPeriods,
+    /// Whether the number of phases can be switched during a transaction
+    ConnectorSwitch3to1PhaseSupported,
+    /// Maximum number of installed charging profiles
+    MaxChargingProfilesInstalled,
+    // Security
+    /// Whether a new Central System root certificate must be signed by the old one
+    AdditionalRootCertificateCheck,
+    /// Basic authentication password of security profiles 1 and 2
+    AuthorizationKey,
+    /// Maximum size in bytes of the certificate chain of a CertificateSigned.req
+    CertificateSignedMaxChainSize,
+    /// Maximum number of installed root certificates
+    CertificateStoreMaxLength,
+    /// Name of the CPO, checked against the Charge Point certificate
+    CpoName,
+    /// Security profile in use, which may only be raised
+    SecurityProfile,
+}
+
+impl ConfigurationKey {
+    /// Feature profile to which the key belongs
+    pub fn profile(&self) -> FeatureProfile { self.definition().0 }
+
+    /// Whether the key may be read and changed by the Central System
+    pub fn accessibility(&self) -> Accessibility { self.definition().1 }
+
+    /// Whether the key cannot be changed by ChangeConfiguration.req
+    pub fn is_read_only(&self) -> bool {
+        self.accessibility() == Accessibility::ReadOnly
+    }
+
+    /// Type of the value of the key
+    pub fn value_type(&self) -> ValueType { self.definition().2 }
+
+    /// Key holding the maximum number of items of this list key, e.g. MeterValuesSampledDataMaxLength for
+    /// MeterValuesSampledData
+    pub fn max_length_key(&self) -> Option<ConfigurationKey> {
+        use ConfigurationKey::*;
+        match self {
+            ConnectorPhaseRotation => Some(ConnectorPhaseRotationMaxLength),
+            MeterValuesAlignedData => Some(MeterValuesAlignedDataMaxLength),
+            MeterValuesSampledData => Some(MeterValuesSampledDataMaxLength),
+            StopTxnAlignedData => Some(StopTxnAlignedDataMaxLength),
+            StopTxnSampledData => Some(StopTxnSampledDataMaxLength),
+            SupportedFeatureProfiles => Some(SupportedFeatureProfilesMaxLength),
+            _ => None,
+        }
+    }
+
+    /// Parse a value as carried in a ChangeConfiguration.req. Booleans are case-insensitive and list items may be surrounded
+    /// by whitespace.
+    pub fn parse(
+        &self,
+        value: &str,
+    ) -> Result<ConfigurationValue, ConfigurationError> {
+        let invalid = || ConfigurationError::InvalidValue {
+            key: *self,
+            value: String::from(value),
+        };
+        let parsed = match self.value_type() {
+            ValueType::Boolean => {
+                match value.trim().to_ascii_lowercase().as_str() {
+                    "true" => ConfigurationValue::Boolean(true),
+                    "false" => ConfigurationValue::Boolean(false),
+                    _ => return Err(invalid()),
+                }
+            },
+            ValueType::Integer => ConfigurationValue::Integer(
+                value.trim().parse().map_err(|_| invalid())?,
+            ),
+            ValueType::String => {
+                ConfigurationValue::String(String::from(value))
+            },
+            ValueType::MeasurandList => ConfigurationValue::Measurands(
+                csl(value)
+                    .map(parse_measurand)
+                    .collect::<Option<_>>()
+                    .ok_or_else(invalid)?,
+            ),
+            ValueType::PhaseRotationList => {
+                list(value, is_phase_rotation).ok_or_else(invalid)?
+            },
+            // The security extension is not a feature profile which can be reported
+            ValueType::ProfileList => list(value, |item| {
+                item.parse::<FeatureProfile>()
+                    .is_ok_and(|profile| profile != FeatureProfile::Security)
+            })
+            .ok_or_else(invalid)?,
+            ValueType::ChargingRateUnitList => {
+                list(value, |item| matches!(item, "Current" | "Power"))
+                    .ok_or_else(invalid)?
+            },
+        };
+
+        let valid = match (self, &parsed) {
+            (
+                ConfigurationKey::AuthorizationKey,
+                ConfigurationValue::String(key),
+            ) => is_valid_authorization_key(key),
+            (
+                ConfigurationKey::SecurityProfile,
+                ConfigurationValue::Integer(profile),
+            ) => *profile <= 3,
+            _ => true,
+        };
+        if !valid {
+            return Err(invalid());
+        }
+        Ok(parsed)
+    }
+
+    fn definition(&self) -> (FeatureProfile, Accessibility, ValueType) {
+        use Accessibility::*;
+        use ConfigurationKey::*;
+        use FeatureProfile::*;
+        match self {
+            AllowOfflineTxForUnknownId
+            | AuthorizationCacheEnabled
+            | AuthorizeRemoteTxRequests
+            | LocalAuthorizeOffline
+            | LocalPreAuthorize
+            | StopTransactionOnEVSideDisconnect
+            | StopTransactionOnInvalidId
+            | UnlockConnectorOnEVSideDisconnect => {
+                (Core, ReadWrite, ValueType::Boolean)
+            },
+            BlinkRepeat
+            | ClockAlignedDataInterval
+            | ConnectionTimeOut
+            | HeartbeatInterval
+            | LightIntensity
+            | MaxEnergyOnInvalidId
+            | MeterValueSampleInterval
+            | MinimumStatusDuration
+            | ResetRetries
+            | TransactionMessageAttempts
+            | TransactionMessageRetryInterval
+            | WebSocketPingInterval => (Core, ReadWrite, ValueType::Integer),
+            ConnectorPhaseRotationMaxLength
+            | GetConfigurationMaxKeys
+            | MeterValuesAlignedDataMaxLength
+            | MeterValuesSampledDataMaxLength
+            | NumberOfConnectors
+            | StopTxnAlignedDataMaxLength
+            | StopTxnSampledDataMaxLength
+            | SupportedFeatureProfilesMaxLength => {
+                (Core, ReadOnly, ValueType::Integer)
+            },
+            MeterValuesAlignedData
+            | MeterValuesSampledData
+            | StopTxnAlignedData
+            | StopTxnSampledData => (Core, ReadWrite, ValueType::MeasurandList),
+            ConnectorPhaseRotation => {
+                (Core, ReadWrite, ValueType::PhaseRotationList)
+            },
+            SupportedFeatureProfiles => {
+                (Core, ReadOnly, ValueType::ProfileList)
+            },
+            LocalAuthListEnabled => {
+                (LocalAuthListManagement, ReadWrite, ValueType::Boolean)
+            },
+            LocalAuthListMaxLength | SendLocalListMaxLength => {
+                (LocalAuthListManagement, ReadOnly, ValueType::Integer)
+            },
+            ReserveConnectorZeroSupported => {
+                (Reservation, ReadOnly, ValueType::Boolean)
+            },
+            ChargeProfileMaxStackLevel
+            | ChargingScheduleMaxPeriods
+            | MaxChargingProfilesInstalled => {
+                (SmartCharging, ReadOnly, ValueType::Integer)
+            },
+            ChargingScheduleAllowedChargingRateUnit => {
+                (SmartCharging, ReadOnly, ValueType::ChargingRateUnitList)
+            },
+            ConnectorSwitch3to1PhaseSupported => {
+                (SmartCharging, ReadOnly, ValueType::Boolean)
+            },
+            AdditionalRootCertificateCheck => {
+                (Security, ReadOnly, ValueType::Boolean)
+            },
+            AuthorizationKey => (Security, WriteOnly, ValueType::String),
+            CertificateSignedMaxChainSize | CertificateStoreMaxLength => {
+                (Security, ReadOnly, ValueType::Integer)
+            },
+            CpoName => (Security, ReadWrite, ValueType::String),
+            SecurityProfile => (Security, ReadWrite, ValueType::Integer),
+        }
+    }
+}
+
+/// Parsed value of a configuration key
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigurationValue {
+    /// Value of a [ValueType::Boolean] key
+    Boolean(bool),
+    /// Value of a [ValueType::Integer] key
+    Integer(u32),
+    /// Value of a [ValueType::String] key
+    String(String),
+    /// Value of a [ValueType::MeasurandList] key
+    Measurands(Vec<SampledMeasurand>),
+    /// Value of any other list key, with surrounding whitespace removed from the items
+    List(Vec<String>),
+}
+
+impl ConfigurationValue {
+    /// Whether the value can be held by a key of type `value_type`
+    pub fn is_of_type(&self, value_type: ValueType) -> bool {
+        matches!(
+            (self, value_type),
+            (ConfigurationValue::Boolean(_), ValueType::Boolean)
+                | (ConfigurationValue::Integer(_), ValueType::Integer)
+                | (ConfigurationValue::String(_), ValueType::String)
+                | (ConfigurationValue::Measurands(_), ValueType::MeasurandList)
+                | (
+                    ConfigurationValue::List(_),
+                    ValueType::PhaseRotationList
+                        | ValueType::ProfileList
+                        | ValueType::ChargingRateUnitList
+                )
+        )
+    }
+
+    /// Number of items of a list, or 1 for any other value
+    pub fn item_count(&self) -> usize {
+        match self {
+            ConfigurationValue::Measurands(measurands) => measurands.len(),
+            ConfigurationValue::List(items) => items.len(),
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for ConfigurationValue {
+    /// The value as reported in a GetConfiguration.conf
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigurationValue::Boolean(value) => write!(f, "{}", value),
+            ConfigurationValue::Integer(value) => write!(f, "{}", value),
+            ConfigurationValue::String(value) => f.write_str(value),
+            ConfigurationValue::Measurands(measurands) => {
+                let names: Vec<String> =
+                    measurands.iter().map(measurand_name).collect();
+                f.write_str(&names.join(","))
+            },
+            ConfigurationValue::List(items) => f.write_str(&items.join(",")),
+        }
+    }
+}
+
+/// Configuration keys supported by a Charge Point. See the [module documentation](self).
+#[derive(Debug, Clone, Default)]
+pub struct ConfigurationStore {
+    values: BTreeMap<ConfigurationKey, Option<ConfigurationValue>>,
+    reboot_required: BTreeSet<ConfigurationKey>,
+}
+
+impl ConfigurationStore {
+    /// Answer RebootRequired instead of Accepted when `key` is changed
+    pub fn reboot_required(mut self, key: ConfigurationKey) -> Self {
+        self.reboot_required.insert(key);
+        self
+    }
+
+    /// Support `key` without setting a value, which GetConfiguration.conf then reports without a value
+    pub fn support(&mut self, key: ConfigurationKey) {
+        self.values.entry(key).or_insert(None);
+    }
+
+    /// Support `key` with `value`, returning the previous value. Read-only keys are set this way by the Charge Point itself.
+    pub fn insert(
+        &mut self,
+        key: ConfigurationKey,
+        value: ConfigurationValue,
+    ) -> Result<Option<ConfigurationValue>, ConfigurationError> {
+        if !value.is_of_type(key.value_type()) {
+            return Err(ConfigurationError::WrongType {
+                key,
+                expected: key.value_type(),
+            });
+        }
+        if let Some(max_length) = self.max_length(key) {
+            if value.item_count() > max_length as usize {
+                return Err(ConfigurationError::TooManyItems {
+                    key,
+                    max_length,
+                });
+            }
+        }
+        Ok(self.values.insert(key, Some(value)).flatten())
+    }
+
+    /// Value of a key, if it is supported and set
+    pub fn get(&self, key: ConfigurationKey) -> Option<&ConfigurationValue> {
+        self.values.get(&key).and_then(Option::as_ref)
+    }
+
+    /// Whether the Charge Point supports a key
+    pub fn is_supported(&self, key: ConfigurationKey) -> bool {
+        self.values.contains_key(&key)
+    }
+
+    /// Answer a GetConfiguration.req with the requested keys, or all supported keys if none are requested. The value of a
+    /// write-only key is never reported. A request for more keys than GetConfigurationMaxKeys allows is refused with
+    /// [OCPPCallErrorCode::OccurenceConstraintViolation].
+    pub fn get_configuration(
+        &self,
+        req: &GetConfigurationRequest,
+    ) -> Result<GetConfigurationResponse, OCPPCallErrorCode> {
+        let requested = req.key.as_deref().unwrap_or_default();
+        if requested.is_empty() {
+            return Ok(GetConfigurationResponse {
+                configuration_key: Some(
+                    self.values
+                        .iter()
+                        .map(|(key, value)| key_value(*key, value.as_ref()))
+                        .collect(),
+                ),
+                unknown_key: None,
+            });
+        }
+        if let Some(ConfigurationValue::Integer(max_keys)) =
+            self.get(ConfigurationKey::GetConfigurationMaxKeys)
+        {
+            if requested.len() > *max_keys as usize {
+                tracing::warn!(
+                    "OCPP GetConfiguration for {} keys exceeds GetConfigurationMaxKeys",
+                    requested.len()
+                );
+                return Err(OCPPCallErrorCode::OccurenceConstraintViolation);
+            }
+        }
+
+        let mut configuration_key = Vec::new();
+        let mut unknown_key = Vec::new();
+        for name in requested {
+            let supported = name
+                .parse::<ConfigurationKey>()
+                .ok()
+                .and_then(|key| Some((key, self.values.get(&key)?)));
+            match supported {
+                Some((key, value)) => {
+                    configuration_key.push(key_value(key, value.as_ref()))
+                },
+                None => unknown_key.push(name.clone()),
+            }
+        }
+        Ok(GetConfigurationResponse {
+            configuration_key: Some(configuration_key)
+                .filter(|keys| !keys.is_empty()),
+            unknown_key: Some(unknown_key).filter(|keys| !keys.is_empty()),
+        })
+    }
+
+    /// Apply a ChangeConfiguration.req, changing the value only if the status is Accepted or RebootRequired
+    pub fn change_configuration(
+        &mut self,
+        req: &ChangeConfigurationRequest,
+    ) -> ChangeConfigurationResponse {
+        ChangeConfigurationResponse {
+            status: self.change(req),
+        }
+    }
+
+    fn change(
+        &mut self,
+        req: &ChangeConfigurationRequest,
+    ) -> ChangeConfigurationStatus {
+        let Ok(key) = req.key.parse::<ConfigurationKey>() else {
+            return ChangeConfigurationStatus::NotSupported;
+        };
+        if !self.is_supported(key) {
+            return ChangeConfigurationStatus::NotSupported;
+        }
+        if key.is_read_only() {
+            tracing::warn!(
+                "OCPP Change of read-only configuration key {}",
+                key
+            );
+            return ChangeConfigurationStatus::Rejected;
+        }
+        let value = match key.parse(&req.value) {
+            Ok(value) => value,
+            Err(err) => {
+                tracing::warn!("OCPP Configuration change rejected: {}", err);
+                return ChangeConfigurationStatus::Rejected;
+            },
+        };
+        if key == ConfigurationKey::SecurityProfile
+            && self
+                .get(key)
+                .is_some_and(|current| is_lower_profile(&value, current))
+        {
+            tracing::warn!("OCPP Lowering of SecurityProfile rejected");
+            return ChangeConfigurationStatus::Rejected;
+        }
+        if let Err(err) = self.insert(key, value) {
+            tracing::warn!("OCPP Configuration change rejected: {}", err);
+            return ChangeConfigurationStatus::Rejected;
+        }
+
+        if self.reboot_required.contains(&key) {
+            ChangeConfigurationStatus::RebootRequired
+        } else {
+            ChangeConfigurationStatus::Accepted
+        }
+    }
+
+    fn max_length(&self, key: ConfigurationKey) -> Option<u32> {
+        match self.get(key.max_length_key()?)? {
+            ConfigurationValue::Integer(max_length) => Some(*max_length),
+            _ => None,
+        }
+    }
+}
+
+fn key_value(
+    key: ConfigurationKey,
+    value: Option<&ConfigurationValue>,
+) -> KeyValue {
+    KeyValue {
+        key: key.to_string(),
+        readonly: key.is_read_only(),
+        value: value
+            .filter(|_| key.accessibility() != Accessibility::WriteOnly)
+            .map(ToString::to_string),
+    }
+}
+
+fn is_lower_profile(
+    value: &ConfigurationValue,
+    current: &ConfigurationValue,
+) -> bool {
+    matches!(
+        (value, current),
+        (ConfigurationValue::Integer(new), ConfigurationValue::Integer(current))
+            if new < current
+    )
+}
+
+/// Items of a comma separated list, an empty value being an empty list
+fn csl(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(str::trim).filter(|_| !value.trim().is_empty())
+}
+
+fn list(
+    value: &str,
+    valid: impl Fn(&str) -> bool,
+) -> Option<ConfigurationValue> {
+    csl(value)
+        .map(|item| valid(item).then(|| String::from(item)))
+        .collect::<Option<_>>()
+        .map(ConfigurationValue::List)
+}
+
+/// Whether `item` is a phase rotation of a connector, e.g. `1.RST`
+fn is_phase_rotation(item: &str) -> bool {
+    let Some((connector_id, rotation)) = item.split_once('.') else {
+        return false;
+    };
+    connector_id.parse::<u32>().is_ok()
+        && matches!(
+            rotation,
+            "NotApplicable"
+                | "Unknown"
+                | "RST"
+                | "RTS"
+                | "SRT"
+                | "STR"
+                | "TRS"
+                | "TSR"
+        )
+}
+
+fn parse_measurand(item: &str) -> Option<SampledMeasurand> {
+    serde_json::from_value(serde_json::Value::from(item)).ok()
+}
+
+fn measurand_name(measurand: &SampledMeasurand) -> String {
+    match serde_json::to_value(measurand) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => measurand.to_string(),
+    }
+}
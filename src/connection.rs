@@ -0,0 +1,267 @@
+//! OCPP-J message exchange over an established WebSocket
+//!
+//! Shared by the transports: incoming calls are passed to a [Dispatch] and answered as each completes, without holding up
+//! other messages, while outgoing calls are queued as [Command]s and sent one at a time, each awaiting its response or
+//! timeout before the next is sent.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+use futures_util::stream::FuturesUnordered;
+use futures_util::{FutureExt, SinkExt, StreamExt};
+use ocpp_json_validate::JsonValidateError;
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::WebSocketStream;
+
+use crate::{
+    parse_call, MessageIdGenerator, OCPPCall, OCPPCallAction, OCPPCallError,
+    OCPPCallErrorCode, OCPPCallResult, OCPPCallResultPayload,
+    OCPPCallResultUnknown, OCPPRequest, ParseCallError, PendingCalls,
+};
+
+/// WebSocket subprotocol for OCPP 1.6 JSON
+pub const OCPP_SUBPROTOCOL: &str = "ocpp1.6";
+
+/// Errors returned when sending a call over a connection
+#[derive(Debug, Error)]
+pub enum RequestError {
+    /// No connection to the given charge point
+    #[error("charge point {0} is not connected")]
+    NotConnected(String),
+    /// The action cannot be sent in this direction
+    #[error("{0} cannot be sent in this direction")]
+    WrongDirection(OCPPCallAction),
+    /// The request failed schema validation and was not sent
+    #[error("invalid request: {0}")]
+    InvalidRequest(JsonValidateError),
+    /// No response was received within the call timeout
+    #[error("call timed out")]
+    Timeout,
+    /// The receiver answered with a call error
+    #[error("call rejected with {}: {}", .0.error_code, .0.error_description)]
+    Rejected(OCPPCallError),
+    /// The response could not be decoded as a response to the call
+    #[error("invalid response: {0}")]
+    InvalidResponse(String),
+    /// The connection closed before a response was received
+    #[error("connection closed")]
+    ConnectionClosed,
+}
+
+/// Handling of incoming calls on a connection
+pub(crate) trait Dispatch: Send {
+    /// Answer an incoming call. The returned future may run concurrently with those of other calls.
+    fn dispatch(
+        &mut self,
+        call: OCPPCall,
+    ) -> impl Future<Output = Result<OCPPCallResult, OCPPCallError>> + Send + 'static;
+}
+
+type Reply = oneshot::Sender<Result<OCPPCallResultPayload, RequestError>>;
+
+/// Frame answering an incoming call, once the call has been handled
+type Response = BoxFuture<'static, String>;
+
+/// Instruction to a running connection
+pub(crate) enum Command {
+    /// Send a call and reply with its response
+    Call {
+        payload: crate::OCPPCallPayload,
+        reply: Reply,
+    },
+}
+
+/// Queue a call on a connection and await its typed response
+pub(crate) async fn send_call<R: OCPPRequest>(
+    commands: &mpsc::Sender<Command>,
+    req: R,
+) -> Result<R::Response, RequestError> {
+    req.validate().map_err(RequestError::InvalidRequest)?;
+
+    let (reply, response) = oneshot::channel();
+    commands
+        .send(Command::Call {
+            payload: req.into(),
+            reply,
+        })
+        .await
+        .map_err(|_| RequestError::ConnectionClosed)?;
+
+    let payload =
+        response.await.map_err(|_| RequestError::ConnectionClosed)??;
+    R::Response::try_from(payload).map_err(|payload| {
+        RequestError::InvalidResponse(format!(
+            "expected {} response, got {}",
+            R::ACTION,
+            payload
+        ))
+    })
+}
+
+/// Exchange messages on `ws` until it closes
+pub(crate) async fn run<S, D>(
+    mut ws: WebSocketStream<S>,
+    commands: &mut mpsc::Receiver<Command>,
+    ids: Arc<dyn MessageIdGenerator>,
+    call_timeout: Duration,
+    mut dispatch: D,
+) -> Result<(), tungstenite::Error>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+    D: Dispatch,
+{
+    let mut pending = PendingCalls::new(call_timeout);
+    let mut reply: Option<Reply> = None;
+    let mut commands_open = true;
+    let mut responses = FuturesUnordered::<Response>::new();
+
+    loop {
+        let deadline = pending
+            .outstanding()
+            .map(|call| call.sent_at + call_timeout)
+            .unwrap_or_else(std::time::Instant::now);
+
+        tokio::select! {
+            message = ws.next() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => continue,
+                    Some(Err(e)) => return Err(e),
+                };
+                if let Some(response) =
+                    receive(&text, &mut pending, &mut reply, &mut dispatch)
+                {
+                    responses.push(response);
+                }
+            },
+            Some(response) = responses.next(), if !responses.is_empty() => {
+                ws.send(Message::Text(response)).await?;
+            },
+            command = commands.recv(), if commands_open && reply.is_none() => {
+                let Some(Command::Call { payload, reply: sender }) = command else {
+                    commands_open = false;
+                    continue;
+                };
+                let call: OCPPCall = (ids.next_id(), payload).into();
+                let text = serde_json::to_string(&call)
+                    .expect("OCPP call should serialize");
+                match pending.push(&call) {
+                    Ok(()) => reply = Some(sender),
+                    Err(e) => {
+                        tracing::error!("OCPP Call not sent: {}", e);
+                        continue;
+                    },
+                }
+                ws.send(Message::Text(text)).await?;
+            },
+            _ = tokio::time::sleep_until(deadline.into()), if reply.is_some() => {
+                if pending.expire().is_some() {
+                    if let Some(sender) = reply.take() {
+                        let _ = sender.send(Err(RequestError::Timeout));
+                    }
+                }
+            },
+        }
+    }
+
+    let _ = ws.close(None).await;
+    Ok(())
+}
+
+/// Handle an incoming text frame, returning the frame to send in reply
+fn receive<D: Dispatch>(
+    text: &str,
+    pending: &mut PendingCalls,
+    reply: &mut Option<Reply>,
+    dispatch: &mut D,
+) -> Option<Response> {
+    let message_type_id = serde_json::from_str::<serde_json::Value>(text)
+        .ok()
+        .and_then(|frame| frame.get(0).and_then(serde_json::Value::as_u64));
+
+    match message_type_id {
+        Some(3) => {
+            let result = serde_json::from_str::<OCPPCallResultUnknown>(text)
+                .map_err(|e| RequestError::InvalidResponse(e.to_string()))
+                .and_then(|unknown| {
+                    pending.resolve_result(unknown).map_err(|e| {
+                        RequestError::InvalidResponse(e.to_string())
+                    })
+                });
+            respond(pending, reply, result.map(|result| result.payload));
+            None
+        },
+        Some(4) => {
+            let result = match serde_json::from_str::<OCPPCallError>(text) {
+                Ok(error) => match pending.resolve_error(&error) {
+                    Ok(_) => Err(RequestError::Rejected(error)),
+                    Err(e) => Err(RequestError::InvalidResponse(e.to_string())),
+                },
+                Err(e) => Err(RequestError::InvalidResponse(e.to_string())),
+            };
+            respond(pending, reply, result);
+            None
+        },
+        _ => {
+            let error = match parse_call(text) {
+                Ok(call) => {
+                    let response = dispatch.dispatch(call);
+                    return Some(
+                        async move {
+                            match response.await {
+                                Ok(result) => serde_json::to_string(&result),
+                                Err(error) => serde_json::to_string(&error),
+                            }
+                            .expect("OCPP response should serialize")
+                        }
+                        .boxed(),
+                    );
+                },
+                Err(ParseCallError::Invalid(error)) => error,
+                Err(ParseCallError::NotACall(_)) => return None,
+            };
+            let response = serde_json::to_string(&error)
+                .expect("OCPP response should serialize");
+            Some(std::future::ready(response).boxed())
+        },
+    }
+}
+
+/// Pass the outcome of the outstanding call to its caller. Responses which do not belong to the outstanding call are only logged.
+fn respond(
+    pending: &PendingCalls,
+    reply: &mut Option<Reply>,
+    result: Result<OCPPCallResultPayload, RequestError>,
+) {
+    if pending.outstanding().is_some() {
+        if let Err(e) = result {
+            tracing::warn!("OCPP Response ignored: {}", e);
+        }
+        return;
+    }
+    match reply.take() {
+        Some(sender) => {
+            let _ = sender.send(result);
+        },
+        None => {
+            if let Err(e) = result {
+                tracing::warn!("OCPP Response ignored: {}", e);
+            }
+        },
+    }
+}
+
+/// Error sent in reply to a call which the receiving side never handles
+pub(crate) fn not_supported(call: &OCPPCall) -> OCPPCallError {
+    tracing::warn!(
+        "OCPP Request in wrong direction: {}",
+        call.payload.action()
+    );
+    OCPPCallError::from_call(&call.unique_id, OCPPCallErrorCode::NotSupported)
+}
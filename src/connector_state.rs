@@ -0,0 +1,266 @@
+//! Connector status state machine
+//!
+//! [ConnectorState] follows the status of a connector, or of the Charge Point main controller (connector 0), and only allows the
+//! transitions of the OCPP 1.6 state table. A Central System can use it to police the StatusNotification.req of a Charge Point,
+//! and a simulator to drive its connectors through valid transitions.
+//!
+//! Transitions are named after the cell of the state table, a letter for the current status and a digit for the new one:
+//!
+//! |                   | 1 Available | 2 Preparing | 3 Charging | 4 SuspendedEV | 5 SuspendedEVSE | 6 Finishing | 7 Reserved | 8 Unavailable | 9 Faulted |
+//! |-------------------|:-:|:-:|:-:|:-:|:-:|:-:|:-:|:-:|:-:|
+//! | **A** Available     |   | A2 | A3 | A4 | A5 |    | A7 | A8 | A9 |
+//! | **B** Preparing     | B1 |   | B3 | B4 | B5 | B6 |    |    | B9 |
+//! | **C** Charging      | C1 |    |   | C4 | C5 | C6 |    | C8 | C9 |
+//! | **D** SuspendedEV   | D1 |    | D3 |   | D5 | D6 |    | D8 | D9 |
+//! | **E** SuspendedEVSE | E1 |    | E3 | E4 |   | E6 |    | E8 | E9 |
+//! | **F** Finishing     | F1 | F2 |    |    |    |   |    | F8 | F9 |
+//! | **G** Reserved      | G1 | G2 |    |    |    |    |   | G8 | G9 |
+//! | **H** Unavailable   | H1 | H2 | H3 | H4 | H5 |    |    |   | H9 |
+//! | **I** Faulted       | I1 | I2 | I3 | I4 | I5 | I6 | I7 | I8 |   |
+//!
+//! Connector 0 only reports Available, Unavailable and Faulted, and may move freely between them. Reporting the current status
+//! again, e.g. with a new error code, is not a transition and is always allowed.
+//!
+//! # Example
+//! ```
+//! # fn ocpp_connector_state_example() -> Result<(), Box<dyn std::error::Error>> {
+//! use ocpp::*;
+//!
+//! let mut connector = ConnectorState::new(1);
+//! connector.transition(StatusNotificationStatus::Preparing)?;
+//! connector.transition(StatusNotificationStatus::Charging)?;
+//!
+//! // C7 is not in the state table
+//! let err = connector.transition(StatusNotificationStatus::Reserved).unwrap_err();
+//! println!("{}", err);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::{
+    StatusNotificationErrorCode, StatusNotificationRequest,
+    StatusNotificationStatus,
+};
+
+/// Number of error reports kept by a [ConnectorState]
+pub const ERROR_HISTORY_LENGTH: usize = 32;
+
+/// Allowed new statuses of a physical connector for each current status, numbered and ordered as in the state table
+const TRANSITIONS: [&[u8]; 9] = [
+    &[2, 3, 4, 5, 7, 8, 9],    // A Available
+    &[1, 3, 4, 5, 6, 9],       // B Preparing
+    &[1, 4, 5, 6, 8, 9],       // C Charging
+    &[1, 3, 5, 6, 8, 9],       // D SuspendedEV
+    &[1, 3, 4, 6, 8, 9],       // E SuspendedEVSE
+    &[1, 2, 8, 9],             // F Finishing
+    &[1, 2, 8, 9],             // G Reserved
+    &[1, 2, 3, 4, 5, 9],       // H Unavailable
+    &[1, 2, 3, 4, 5, 6, 7, 8], // I Faulted
+];
+
+/// Errors returned by [ConnectorState]
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ConnectorStateError {
+    /// The StatusNotification.req is for another connector
+    #[error("status of connector {actual} reported for connector {expected}")]
+    WrongConnector {
+        /// Connector followed by the state machine
+        expected: u32,
+        /// Connector of the StatusNotification.req
+        actual: u32,
+    },
+    /// Connector 0 only reports Available, Unavailable and Faulted
+    #[error("connector 0 cannot be {0}")]
+    InvalidMainControllerStatus(StatusNotificationStatus),
+    /// The transition is not in the state table
+    #[error("transition {transition} from {from} to {to} is not allowed")]
+    IllegalTransition {
+        /// Name of the transition in the state table, e.g. `A6`
+        transition: String,
+        /// Current status
+        from: StatusNotificationStatus,
+        /// Rejected new status
+        to: StatusNotificationStatus,
+    },
+}
+
+/// An error or vendor specific error reported in a StatusNotification.req
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorReport {
+    /// Status reported with the error
+    pub status: StatusNotificationStatus,
+    /// Reported error code
+    pub error_code: StatusNotificationErrorCode,
+    /// Additional free format information related to the error
+    pub info: Option<String>,
+    /// Vendor of the vendor specific error code
+    pub vendor_id: Option<String>,
+    /// Vendor specific error code
+    pub vendor_error_code: Option<String>,
+    /// Time for which the error was reported
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// Status of a single connector. See the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct ConnectorState {
+    connector_id: u32,
+    status: StatusNotificationStatus,
+    error_code: StatusNotificationErrorCode,
+    errors: VecDeque<ErrorReport>,
+}
+
+impl ConnectorState {
+    /// Create the state of a connector, or of the main controller for connector 0, starting Available with no error
+    pub fn new(connector_id: u32) -> Self {
+        ConnectorState {
+            connector_id,
+            status: StatusNotificationStatus::Available,
+            error_code: StatusNotificationErrorCode::NoError,
+            errors: VecDeque::new(),
+        }
+    }
+
+    /// Id of the connector
+    pub fn connector_id(&self) -> u32 { self.connector_id }
+
+    /// Current status
+    pub fn status(&self) -> &StatusNotificationStatus { &self.status }
+
+    /// Most recently reported error code
+    pub fn error_code(&self) -> &StatusNotificationErrorCode {
+        &self.error_code
+    }
+
+    /// Reported errors, oldest first. Only the last [ERROR_HISTORY_LENGTH] are kept.
+    pub fn error_history(&self) -> impl Iterator<Item = &ErrorReport> {
+        self.errors.iter()
+    }
+
+    /// Whether `from` may be followed by `to` on `connector_id`
+    pub fn is_allowed(
+        connector_id: u32,
+        from: &StatusNotificationStatus,
+        to: &StatusNotificationStatus,
+    ) -> bool {
+        if connector_id == 0 {
+            return is_main_controller_status(to);
+        }
+        from == to || TRANSITIONS[index(from)].contains(&(index(to) as u8 + 1))
+    }
+
+    /// Move to `to`, failing if the transition is not allowed
+    pub fn transition(
+        &mut self,
+        to: StatusNotificationStatus,
+    ) -> Result<(), ConnectorStateError> {
+        self.check(&to)?;
+        self.status = to;
+        Ok(())
+    }
+
+    /// Apply a StatusNotification.req, failing if it is for another connector or the transition is not allowed. Errors and
+    /// vendor specific errors are added to the [error history](Self::error_history).
+    pub fn apply(
+        &mut self,
+        req: &StatusNotificationRequest,
+    ) -> Result<(), ConnectorStateError> {
+        if req.connector_id != self.connector_id {
+            return Err(ConnectorStateError::WrongConnector {
+                expected: self.connector_id,
+                actual: req.connector_id,
+            });
+        }
+        self.check(&req.status)?;
+
+        if req.error_code != StatusNotificationErrorCode::NoError
+            || req.vendor_error_code.is_some()
+        {
+            if self.errors.len() == ERROR_HISTORY_LENGTH {
+                self.errors.pop_front();
+            }
+            self.errors.push_back(ErrorReport {
+                status: req.status.clone(),
+                error_code: req.error_code.clone(),
+                info: req.info.clone(),
+                vendor_id: req.vendor_id.clone(),
+                vendor_error_code: req.vendor_error_code.clone(),
+                timestamp: req.timestamp,
+            });
+        }
+        self.status = req.status.clone();
+        self.error_code = req.error_code.clone();
+        Ok(())
+    }
+
+    /// Set the status without checking the transition, e.g. to the status reported after a reconnection, since transitions
+    /// which happened while offline are not reported
+    pub fn reset(&mut self, status: StatusNotificationStatus) {
+        self.status = status;
+    }
+
+    /// StatusNotification.req reporting the current status and error code
+    pub fn status_notification(&self) -> StatusNotificationRequest {
+        StatusNotificationRequest {
+            connector_id: self.connector_id,
+            error_code: self.error_code.clone(),
+            info: None,
+            status: self.status.clone(),
+            timestamp: Some(Utc::now()),
+            vendor_id: None,
+            vendor_error_code: None,
+        }
+    }
+
+    fn check(
+        &self,
+        to: &StatusNotificationStatus,
+    ) -> Result<(), ConnectorStateError> {
+        if self.connector_id == 0 && !is_main_controller_status(to) {
+            return Err(ConnectorStateError::InvalidMainControllerStatus(
+                to.clone(),
+            ));
+        }
+        if !Self::is_allowed(self.connector_id, &self.status, to) {
+            return Err(ConnectorStateError::IllegalTransition {
+                transition: format!(
+                    "{}{}",
+                    (b'A' + index(&self.status) as u8) as char,
+                    index(to) + 1
+                ),
+                from: self.status.clone(),
+                to: to.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn is_main_controller_status(status: &StatusNotificationStatus) -> bool {
+    matches!(
+        status,
+        StatusNotificationStatus::Available
+            | StatusNotificationStatus::Unavailable
+            | StatusNotificationStatus::Faulted
+    )
+}
+
+/// Position of a status in the state table
+fn index(status: &StatusNotificationStatus) -> usize {
+    match status {
+        StatusNotificationStatus::Available => 0,
+        StatusNotificationStatus::Preparing => 1,
+        StatusNotificationStatus::Charging => 2,
+        StatusNotificationStatus::SuspendedEV => 3,
+        StatusNotificationStatus::SuspendedEVSE => 4,
+        StatusNotificationStatus::Finishing => 5,
+        StatusNotificationStatus::Reserved => 6,
+        StatusNotificationStatus::Unavailable => 7,
+        StatusNotificationStatus::Faulted => 8,
+    }
+}
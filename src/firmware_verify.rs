@@ -0,0 +1,129 @@
+//! Offline verification of signed firmware
+//!
+//! Enabled with the `firmware-verify` feature. Provides checks on a [Firmware] from a
+//! [SignedUpdateFirmware.req](crate::server_init::signed_update_firmware) so that a Central System or simulator can exercise the
+//! complete signed firmware flow without a Charge Point.
+//!
+//! The signing certificate is expected as a PEM encoded X.509 certificate and the signature as base64, as they appear in the
+//! request. RSA (PSS or PKCS#1 v1.5 with SHA-256) and ECDSA (P-256 with SHA-256, P-384 with SHA-384) keys are supported.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use ring::signature::{self, UnparsedPublicKey, VerificationAlgorithm};
+use thiserror::Error;
+use x509_parser::oid_registry::{
+    OID_EC_P256, OID_KEY_TYPE_EC_PUBLIC_KEY, OID_NIST_EC_P384,
+    OID_PKCS1_RSAENCRYPTION,
+};
+use x509_parser::pem::parse_x509_pem;
+use x509_parser::x509::SubjectPublicKeyInfo;
+
+use crate::server_init::signed_update_firmware::Firmware;
+
+/// Errors returned while verifying a [Firmware]
+#[derive(Debug, Error)]
+pub enum FirmwareVerifyError {
+    /// A certificate could not be decoded from PEM or parsed as X.509
+    #[error("invalid certificate: {0}")]
+    InvalidCertificate(String),
+    /// The signature field is not valid base64
+    #[error("invalid signature encoding: {0}")]
+    InvalidSignatureEncoding(#[from] base64::DecodeError),
+    /// The public key algorithm of the signing certificate is not supported
+    #[error("unsupported public key algorithm")]
+    UnsupportedKeyAlgorithm,
+    /// The signature does not match the firmware
+    #[error("firmware signature is invalid")]
+    InvalidSignature,
+    /// The signing certificate was not issued by the given root certificate
+    #[error("signing certificate was not issued by the root certificate")]
+    UntrustedCertificate,
+    /// The signing certificate is not yet valid or has expired
+    #[error("signing certificate is outside its validity period")]
+    ExpiredCertificate,
+}
+
+impl Firmware {
+    /// Verify `firmware` against [Firmware::signature] using the public key of [Firmware::signing_certificate].
+    ///
+    /// This corresponds to the check a Charge Point performs after download, before reporting SignatureVerified or InvalidSignature.
+    pub fn verify_signature(
+        &self,
+        firmware: &[u8],
+    ) -> Result<(), FirmwareVerifyError> {
+        let signature = STANDARD.decode(self.signature.trim())?;
+        let (_, pem) = parse_x509_pem(self.signing_certificate.as_bytes())
+            .map_err(invalid_certificate)?;
+        let cert = pem.parse_x509().map_err(invalid_certificate)?;
+
+        let key = &cert.public_key().subject_public_key.data;
+        let verified = algorithms(cert.public_key())?.into_iter().any(|alg| {
+            UnparsedPublicKey::new(alg, key)
+                .verify(firmware, &signature)
+                .is_ok()
+        });
+
+        if verified {
+            Ok(())
+        } else {
+            Err(FirmwareVerifyError::InvalidSignature)
+        }
+    }
+
+    /// Verify that [Firmware::signing_certificate] was issued by the PEM encoded `root_certificate` and is currently valid.
+    ///
+    /// This corresponds to the check a Charge Point performs against its ManufacturerRootCertificate before accepting the request.
+    pub fn verify_signing_certificate(
+        &self,
+        root_certificate: &str,
+    ) -> Result<(), FirmwareVerifyError> {
+        let (_, root_pem) = parse_x509_pem(root_certificate.as_bytes())
+            .map_err(invalid_certificate)?;
+        let root = root_pem.parse_x509().map_err(invalid_certificate)?;
+        let (_, pem) = parse_x509_pem(self.signing_certificate.as_bytes())
+            .map_err(invalid_certificate)?;
+        let cert = pem.parse_x509().map_err(invalid_certificate)?;
+
+        if cert.issuer() != root.subject() {
+            return Err(FirmwareVerifyError::UntrustedCertificate);
+        }
+        cert.verify_signature(Some(root.public_key()))
+            .map_err(|_| FirmwareVerifyError::UntrustedCertificate)?;
+        if !cert.validity().is_valid() {
+            return Err(FirmwareVerifyError::ExpiredCertificate);
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid_certificate(e: impl ToString) -> FirmwareVerifyError {
+    FirmwareVerifyError::InvalidCertificate(e.to_string())
+}
+
+/// Candidate signature algorithms for a public key
+fn algorithms(
+    spki: &SubjectPublicKeyInfo,
+) -> Result<Vec<&'static dyn VerificationAlgorithm>, FirmwareVerifyError> {
+    let oid = &spki.algorithm.algorithm;
+    if *oid == OID_PKCS1_RSAENCRYPTION {
+        return Ok(vec![
+            &signature::RSA_PSS_2048_8192_SHA256,
+            &signature::RSA_PKCS1_2048_8192_SHA256,
+        ]);
+    }
+    if *oid == OID_KEY_TYPE_EC_PUBLIC_KEY {
+        let curve =
+            spki.algorithm.parameters.as_ref().and_then(|p| p.as_oid().ok());
+        return match curve {
+            Some(c) if c == OID_EC_P256 => {
+                Ok(vec![&signature::ECDSA_P256_SHA256_ASN1])
+            },
+            Some(c) if c == OID_NIST_EC_P384 => {
+                Ok(vec![&signature::ECDSA_P384_SHA384_ASN1])
+            },
+            _ => Err(FirmwareVerifyError::UnsupportedKeyAlgorithm),
+        };
+    }
+    Err(FirmwareVerifyError::UnsupportedKeyAlgorithm)
+}
@@ -0,0 +1,159 @@
+//! Direction specific handlers for incoming calls
+//!
+//! [OCPPCallResultBuilder](crate::OCPPCallResultBuilder) accepts every call regardless of which side sent it. The traits in this
+//! module only expose the calls that the receiving side can legitimately be sent: [CentralSystemHandler] for a Central System and
+//! [ChargePointHandler] for a Charge Point. A call arriving in the wrong direction is answered with
+//! [OCPPCallErrorCode::NotSupported].
+
+use ocpp_json_validate::JsonValidate;
+
+use crate::*;
+
+/// Declares the direction tables of [OCPPCallAction] and a handler trait for
+/// each receiving side, from the sender each action is tagged with
+macro_rules! direction_handlers {
+    // Sort the actions into those sent by a Charge Point and those sent by
+    // the Central System
+    (@sort [$($charge_point:tt)*] [$($central_system:tt)*]
+        $action:ident($req:ident, $res:ident) $method:ident: ChargePoint,
+        $($rest:tt)*
+    ) => {
+        direction_handlers! { @sort
+            [$($charge_point)* $action($req, $res) $method,]
+            [$($central_system)*]
+            $($rest)*
+        }
+    };
+    (@sort [$($charge_point:tt)*] [$($central_system:tt)*]
+        $action:ident($req:ident, $res:ident) $method:ident: CentralSystem,
+        $($rest:tt)*
+    ) => {
+        direction_handlers! { @sort
+            [$($charge_point)*]
+            [$($central_system)* $action($req, $res) $method,]
+            $($rest)*
+        }
+    };
+    (@sort [$($charge_point:tt)*] [$($central_system:tt)*]
+        $action:ident($req:ident, $res:ident) $method:ident: Both,
+        $($rest:tt)*
+    ) => {
+        direction_handlers! { @sort
+            [$($charge_point)* $action($req, $res) $method,]
+            [$($central_system)* $action($req, $res) $method,]
+            $($rest)*
+        }
+    };
+    (@sort [$($charge_point:tt)*] [$($central_system:tt)*]) => {
+        direction_handlers! { @tables
+            [$($charge_point)*]
+            [$($central_system)*]
+        }
+        direction_handlers! { @handler
+            /// Handler for calls received by a Central System, i.e. those initiated by
+            /// the Charge Point (see [point_init](crate::point_init)) plus DataTransfer.
+            ///
+            /// Behaves as [OCPPCallResultBuilder], except that calls only sent by a
+            /// Central System, such as Reset, are never dispatched to the handler.
+            CentralSystemHandler [$($charge_point)*]
+        }
+        direction_handlers! { @handler
+            /// Handler for calls received by a Charge Point, i.e. those initiated by the
+            /// Central System (see [server_init](crate::server_init)) plus DataTransfer.
+            ///
+            /// Behaves as [OCPPCallResultBuilder], except that calls only sent by a
+            /// Charge Point, such as BootNotification, are never dispatched to the
+            /// handler.
+            ChargePointHandler [$($central_system)*]
+        }
+    };
+
+    (@tables
+        [$($charge_point:ident($cp_req:ident, $cp_res:ident) $cp_method:ident,)*]
+        [$($central_system:ident($cs_req:ident, $cs_res:ident) $cs_method:ident,)*]
+    ) => {
+        impl OCPPCallAction {
+            /// Whether calls of this action are sent by a Charge Point to the
+            /// Central System
+            pub fn is_sent_by_charge_point(&self) -> bool {
+                matches!(self, $(OCPPCallAction::$charge_point)|*)
+            }
+
+            /// Whether calls of this action are sent by the Central System to a
+            /// Charge Point
+            pub fn is_sent_by_central_system(&self) -> bool {
+                matches!(self, $(OCPPCallAction::$central_system)|*)
+            }
+        }
+    };
+
+    (@handler $(#[$meta:meta])* $name:ident
+        [$($action:ident($req:ident, $res:ident) $method:ident,)*]
+    ) => {
+        $(#[$meta])*
+        pub trait $name {
+            $(
+                #[doc = concat!(
+                    "Handle ", stringify!($req),
+                    ". Returns [OCPPCallErrorCode::NotImplemented] by default."
+                )]
+                fn $method(
+                    &mut self,
+                    _req: $req,
+                ) -> Result<$res, OCPPCallErrorCode> {
+                    Err(OCPPCallErrorCode::NotImplemented)
+                }
+            )*
+
+            /// Build [OCPPCallResult] from [OCPPCall]. Calls which are not sent in
+            /// this direction are rejected with [OCPPCallErrorCode::NotSupported].
+            fn build_response(
+                &mut self,
+                call: OCPPCall,
+            ) -> Result<OCPPCallResult, OCPPCallError> {
+                let OCPPCall {
+                    unique_id, payload, ..
+                } = call;
+
+                // Validate incoming payload
+                if let Err(e) = payload.validate() {
+                    tracing::warn!("OCPP Request Invalid: {:?}", e);
+                    return Err(OCPPCallError::from_validate_error(&unique_id, &e));
+                }
+
+                let payload = match payload {
+                    $(
+                        OCPPCallPayload::$action(req) => self
+                            .$method(req)
+                            .map(OCPPCallResultPayload::$action)
+                            .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+                    )*
+                    other => {
+                        tracing::warn!("OCPP Request in wrong direction: {}", other);
+                        return Err(OCPPCallError::from_call(
+                            &unique_id,
+                            OCPPCallErrorCode::NotSupported,
+                        ));
+                    },
+                };
+
+                // Validate outgoing payload
+                if let Err(e) = payload.validate() {
+                    tracing::error!("OCPP Response Invalid: {:?}", e);
+                    return Err(OCPPCallError::from_call(
+                        &unique_id,
+                        OCPPCallErrorCode::InternalError,
+                    ));
+                }
+
+                Ok(OCPPCallResult { unique_id, payload })
+            }
+        }
+    };
+
+    ($($actions:tt)*) => {
+        direction_handlers! { @sort [] [] $($actions)* }
+    };
+}
+
+crate::ocpp_actions!(direction_handlers);
@@ -12,7 +12,7 @@
 //! use ocpp::*;
 //!
 //! // Example of storage for known/open calls
-//! let mut known_calls = std::collections::HashMap::<String, OCPPCallAction>::new();
+//! let mut pending_calls = PendingCalls::new(std::time::Duration::from_secs(30));
 //!
 //! // Build a reusable response builder
 //! struct MyResultBuilder {}
@@ -79,17 +79,15 @@
 //!         println!("Call Result: {:#?}", result);
 //!     },
 //!     OCPPMessage::CallResultUnknown(unknown) => {
-//!         // Lookup the call ID in known_calls
-//!         if let Some(action) = known_calls.get(&unknown.unique_id) {
-//!             // Convert and print known call result
-//!             let result = OCPPCallResult::from_unknown(action, unknown)?;
-//!             println!("Call Result: {:#?}", result);
-//!         } else {
-//!             println!("Unknown Call Result: {:#?}", &unknown.unique_id);
+//!         // Match the result to the outstanding call and decode it
+//!         match pending_calls.resolve_result(unknown) {
+//!             Ok(result) => println!("Call Result: {:#?}", result),
+//!             Err(e) => println!("Unexpected Call Result: {}", e),
 //!         }
 //!     },
 //!     OCPPMessage::CallError(err) => {
-//!         println!("Call Error: {:#?}", err);
+//!         let action = pending_calls.resolve_error(&err);
+//!         println!("Call Error: {:#?} for {:?}", err, action);
 //!     }
 //! }
 //! # return Ok(());
@@ -100,20 +98,57 @@
 #[macro_use]
 extern crate lazy_static;
 
+pub mod authorization_cache;
+pub mod charging_profile_store;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod common;
+pub mod composite_schedule;
+pub mod configuration;
+#[cfg(any(feature = "client", feature = "server"))]
+mod connection;
+pub mod connector_state;
+#[cfg(feature = "firmware-verify")]
+pub mod firmware_verify;
+pub mod handler;
+pub mod local_auth_list;
+pub mod message_id;
+pub mod message_queue;
+pub mod pending_calls;
 pub mod point_init;
+#[cfg(any(feature = "client", feature = "server"))]
+pub mod security;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod server_init;
+pub mod transaction_id;
+pub mod transaction_tracker;
 
 #[cfg(test)]
+#[allow(missing_docs)]
 pub mod test;
 
+use std::future::Future;
+
+pub use authorization_cache::*;
+pub use charging_profile_store::*;
 pub use common::*;
+pub use composite_schedule::*;
+pub use configuration::*;
+pub use connector_state::*;
+pub use handler::*;
+pub use local_auth_list::*;
+pub use message_id::*;
+pub use message_queue::*;
 use ocpp_json_validate::JsonValidate;
+pub use pending_calls::*;
 pub use point_init::*;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 pub use server_init::*;
-use strum_macros::Display;
+use strum_macros::{Display, EnumString};
 use thiserror::Error;
+pub use transaction_id::*;
+pub use transaction_tracker::*;
 
 /// Overarching OCPP Message use to encapsulate calls, call results and call errors
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -179,6 +214,14 @@ impl<'de> Deserialize<'de> for OCPPCall {
                 BootNotificationRequest::deserialize(payload_raw)
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
             ),
+            "CancelReservation" => OCPPCallPayload::CancelReservation(
+                CancelReservationRequest::deserialize(payload_raw)
+                    .map_err(|e| de::Error::custom(format!("{}", e)))?,
+            ),
+            "CertificateSigned" => OCPPCallPayload::CertificateSigned(
+                CertificateSignedRequest::deserialize(payload_raw)
+                    .map_err(|e| de::Error::custom(format!("{}", e)))?,
+            ),
             "ChangeAvailability" => OCPPCallPayload::ChangeAvailability(
                 ChangeAvailabilityRequest::deserialize(payload_raw)
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
@@ -199,6 +242,10 @@ impl<'de> Deserialize<'de> for OCPPCall {
                 DataTransferRequest::deserialize(payload_raw)
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
             ),
+            "DeleteCertificate" => OCPPCallPayload::DeleteCertificate(
+                DeleteCertificateRequest::deserialize(payload_raw)
+                    .map_err(|e| de::Error::custom(format!("{}", e)))?,
+            ),
             "DiagnosticsStatusNotification" => {
                 OCPPCallPayload::DiagnosticsStatusNotification(
                     DiagnosticsStatusNotificationRequest::deserialize(
@@ -207,6 +254,12 @@ impl<'de> Deserialize<'de> for OCPPCall {
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
                 )
             },
+            "ExtendedTriggerMessage" => {
+                OCPPCallPayload::ExtendedTriggerMessage(
+                    ExtendedTriggerMessageRequest::deserialize(payload_raw)
+                        .map_err(|e| de::Error::custom(format!("{}", e)))?,
+                )
+            },
             "FirmwareStatusNotification" => {
                 OCPPCallPayload::FirmwareStatusNotification(
                     FirmwareStatusNotificationRequest::deserialize(payload_raw)
@@ -225,14 +278,32 @@ impl<'de> Deserialize<'de> for OCPPCall {
                 GetDiagnosticsRequest::deserialize(payload_raw)
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
             ),
+            "GetInstalledCertificateIds" => {
+                OCPPCallPayload::GetInstalledCertificateIds(
+                    GetInstalledCertificateIdsRequest::deserialize(payload_raw)
+                        .map_err(|e| de::Error::custom(format!("{}", e)))?,
+                )
+            },
             "GetLocalListVersion" => OCPPCallPayload::GetLocalListVersion(
                 GetLocalListVersionRequest::deserialize(payload_raw)
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
             ),
+            "GetLog" => OCPPCallPayload::GetLog(
+                GetLogRequest::deserialize(payload_raw)
+                    .map_err(|e| de::Error::custom(format!("{}", e)))?,
+            ),
             "Heartbeat" => OCPPCallPayload::Heartbeat(
                 HeartbeatRequest::deserialize(payload_raw)
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
             ),
+            "InstallCertificate" => OCPPCallPayload::InstallCertificate(
+                InstallCertificateRequest::deserialize(payload_raw)
+                    .map_err(|e| de::Error::custom(format!("{}", e)))?,
+            ),
+            "LogStatusNotification" => OCPPCallPayload::LogStatusNotification(
+                LogStatusNotificationRequest::deserialize(payload_raw)
+                    .map_err(|e| de::Error::custom(format!("{}", e)))?,
+            ),
             "MeterValues" => OCPPCallPayload::MeterValues(
                 MeterValuesRequest::deserialize(payload_raw)
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
@@ -247,10 +318,20 @@ impl<'de> Deserialize<'de> for OCPPCall {
                 RemoteStopTransactionRequest::deserialize(payload_raw)
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
             ),
+            "ReserveNow" => OCPPCallPayload::ReserveNow(
+                ReserveNowRequest::deserialize(payload_raw)
+                    .map_err(|e| de::Error::custom(format!("{}", e)))?,
+            ),
             "Reset" => OCPPCallPayload::Reset(
                 ResetRequest::deserialize(payload_raw)
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
             ),
+            "SecurityEventNotification" => {
+                OCPPCallPayload::SecurityEventNotification(
+                    SecurityEventNotificationRequest::deserialize(payload_raw)
+                        .map_err(|e| de::Error::custom(format!("{}", e)))?,
+                )
+            },
             "SendLocalList" => OCPPCallPayload::SendLocalList(
                 SendLocalListRequest::deserialize(payload_raw)
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
@@ -259,6 +340,22 @@ impl<'de> Deserialize<'de> for OCPPCall {
                 SetChargingProfileRequest::deserialize(payload_raw)
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
             ),
+            "SignCertificate" => OCPPCallPayload::SignCertificate(
+                SignCertificateRequest::deserialize(payload_raw)
+                    .map_err(|e| de::Error::custom(format!("{}", e)))?,
+            ),
+            "SignedFirmwareStatusNotification" => {
+                OCPPCallPayload::SignedFirmwareStatusNotification(
+                    SignedFirmwareStatusNotificationRequest::deserialize(
+                        payload_raw,
+                    )
+                    .map_err(|e| de::Error::custom(format!("{}", e)))?,
+                )
+            },
+            "SignedUpdateFirmware" => OCPPCallPayload::SignedUpdateFirmware(
+                SignedUpdateFirmwareRequest::deserialize(payload_raw)
+                    .map_err(|e| de::Error::custom(format!("{}", e)))?,
+            ),
             "StartTransaction" => OCPPCallPayload::StartTransaction(
                 StartTransactionRequest::deserialize(payload_raw)
                     .map_err(|e| de::Error::custom(format!("{}", e)))?,
@@ -289,24 +386,37 @@ impl<'de> Deserialize<'de> for OCPPCall {
                     &[
                         "Authorize",
                         "BootNotification",
+                        "CancelReservation",
+                        "CertificateSigned",
                         "ChangeAvailability",
                         "ChangeConfiguration",
                         "ClearCache",
                         "ClearChargingProfile",
                         "DataTransfer",
+                        "DeleteCertificate",
                         "DiagnosticsStatusNotification",
+                        "ExtendedTriggerMessage",
                         "FirmwareStatusNotification",
                         "GetCompositeSchedule",
                         "GetConfiguration",
                         "GetDiagnostics",
+                        "GetInstalledCertificateIds",
                         "GetLocalListVersion",
+                        "GetLog",
                         "Heartbeat",
+                        "InstallCertificate",
+                        "LogStatusNotification",
                         "MeterValues",
                         "RemoteStartTransaction",
                         "RemoteStopTransaction",
+                        "ReserveNow",
                         "Reset",
+                        "SecurityEventNotification",
                         "SendLocalList",
                         "SetChargingProfile",
+                        "SignCertificate",
+                        "SignedFirmwareStatusNotification",
+                        "SignedUpdateFirmware",
                         "StartTransaction",
                         "StatusNotification",
                         "StopTransaction",
@@ -329,42 +439,7 @@ impl<'de> Deserialize<'de> for OCPPCall {
 impl From<(String, OCPPCallPayload)> for OCPPCall {
     fn from(from: (String, OCPPCallPayload)) -> OCPPCall {
         let (unique_id, payload) = from;
-        let action = String::from(match payload {
-            OCPPCallPayload::Authorize(_) => "Authorize",
-            OCPPCallPayload::BootNotification(_) => "BootNotification",
-            OCPPCallPayload::ChangeAvailability(_) => "ChangeAvailability",
-            OCPPCallPayload::ChangeConfiguration(_) => "ChangeConfiguration",
-            OCPPCallPayload::ClearCache(_) => "ClearCache",
-            OCPPCallPayload::ClearChargingProfile(_) => "ClearChargingProfile",
-            OCPPCallPayload::DataTransfer(_) => "DataTransfer",
-            OCPPCallPayload::DiagnosticsStatusNotification(_) => {
-                "DiagnosticsStatusNotification"
-            },
-            OCPPCallPayload::FirmwareStatusNotification(_) => {
-                "FirmwareStatusNotification"
-            },
-            OCPPCallPayload::GetCompositeSchedule(_) => "GetCompositeSchedule",
-            OCPPCallPayload::GetConfiguration(_) => "GetConfiguration",
-            OCPPCallPayload::GetDiagnostics(_) => "GetDiagnostics",
-            OCPPCallPayload::GetLocalListVersion(_) => "GetLocalListVersion",
-            OCPPCallPayload::Heartbeat(_) => "Heartbeat",
-            OCPPCallPayload::MeterValues(_) => "MeterValues",
-            OCPPCallPayload::RemoteStartTransaction(_) => {
-                "RemoteStartTransaction"
-            },
-            OCPPCallPayload::RemoteStopTransaction(_) => {
-                "RemoteStopTransaction"
-            },
-            OCPPCallPayload::Reset(_) => "Reset",
-            OCPPCallPayload::SendLocalList(_) => "SendLocalList",
-            OCPPCallPayload::SetChargingProfile(_) => "SetChargingProfile",
-            OCPPCallPayload::StartTransaction(_) => "StartTransaction",
-            OCPPCallPayload::StatusNotification(_) => "StatusNotification",
-            OCPPCallPayload::StopTransaction(_) => "StopTransaction",
-            OCPPCallPayload::TriggerMessage(_) => "TriggerMessage",
-            OCPPCallPayload::UnlockConnector(_) => "UnlockConnector",
-            OCPPCallPayload::UpdateFirmware(_) => "UpdateFirmware",
-        });
+        let action = payload.action().to_string();
 
         OCPPCall {
             unique_id,
@@ -464,6 +539,16 @@ impl OCPPCallResult {
                     BootNotificationResponse::deserialize(payload)?,
                 )
             },
+            OCPPCallAction::CancelReservation => {
+                OCPPCallResultPayload::CancelReservation(
+                    CancelReservationResponse::deserialize(payload)?,
+                )
+            },
+            OCPPCallAction::CertificateSigned => {
+                OCPPCallResultPayload::CertificateSigned(
+                    CertificateSignedResponse::deserialize(payload)?,
+                )
+            },
             OCPPCallAction::ChangeAvailability => {
                 OCPPCallResultPayload::ChangeAvailability(
                     ChangeAvailabilityResponse::deserialize(payload)?,
@@ -487,6 +572,11 @@ impl OCPPCallResult {
                     DataTransferResponse::deserialize(payload)?,
                 )
             },
+            OCPPCallAction::DeleteCertificate => {
+                OCPPCallResultPayload::DeleteCertificate(
+                    DeleteCertificateResponse::deserialize(payload)?,
+                )
+            },
             OCPPCallAction::DiagnosticsStatusNotification => {
                 OCPPCallResultPayload::DiagnosticsStatusNotification(
                     DiagnosticsStatusNotificationResponse::deserialize(
@@ -494,6 +584,11 @@ impl OCPPCallResult {
                     )?,
                 )
             },
+            OCPPCallAction::ExtendedTriggerMessage => {
+                OCPPCallResultPayload::ExtendedTriggerMessage(
+                    ExtendedTriggerMessageResponse::deserialize(payload)?,
+                )
+            },
             OCPPCallAction::FirmwareStatusNotification => {
                 OCPPCallResultPayload::FirmwareStatusNotification(
                     FirmwareStatusNotificationResponse::deserialize(payload)?,
@@ -514,14 +609,32 @@ impl OCPPCallResult {
                     GetDiagnosticsResponse::deserialize(payload)?,
                 )
             },
+            OCPPCallAction::GetInstalledCertificateIds => {
+                OCPPCallResultPayload::GetInstalledCertificateIds(
+                    GetInstalledCertificateIdsResponse::deserialize(payload)?,
+                )
+            },
             OCPPCallAction::GetLocalListVersion => {
                 OCPPCallResultPayload::GetLocalListVersion(
                     GetLocalListVersionResponse::deserialize(payload)?,
                 )
             },
+            OCPPCallAction::GetLog => OCPPCallResultPayload::GetLog(
+                GetLogResponse::deserialize(payload)?,
+            ),
             OCPPCallAction::Heartbeat => OCPPCallResultPayload::Heartbeat(
                 HeartbeatResponse::deserialize(payload)?,
             ),
+            OCPPCallAction::InstallCertificate => {
+                OCPPCallResultPayload::InstallCertificate(
+                    InstallCertificateResponse::deserialize(payload)?,
+                )
+            },
+            OCPPCallAction::LogStatusNotification => {
+                OCPPCallResultPayload::LogStatusNotification(
+                    LogStatusNotificationResponse::deserialize(payload)?,
+                )
+            },
             OCPPCallAction::MeterValues => OCPPCallResultPayload::MeterValues(
                 MeterValuesResponse::deserialize(payload)?,
             ),
@@ -535,9 +648,17 @@ impl OCPPCallResult {
                     RemoteStopTransactionResponse::deserialize(payload)?,
                 )
             },
+            OCPPCallAction::ReserveNow => OCPPCallResultPayload::ReserveNow(
+                ReserveNowResponse::deserialize(payload)?,
+            ),
             OCPPCallAction::Reset => OCPPCallResultPayload::Reset(
                 ResetResponse::deserialize(payload)?,
             ),
+            OCPPCallAction::SecurityEventNotification => {
+                OCPPCallResultPayload::SecurityEventNotification(
+                    SecurityEventNotificationResponse::deserialize(payload)?,
+                )
+            },
             OCPPCallAction::SendLocalList => {
                 OCPPCallResultPayload::SendLocalList(
                     SendLocalListResponse::deserialize(payload)?,
@@ -548,6 +669,23 @@ impl OCPPCallResult {
                     SetChargingProfileResponse::deserialize(payload)?,
                 )
             },
+            OCPPCallAction::SignCertificate => {
+                OCPPCallResultPayload::SignCertificate(
+                    SignCertificateResponse::deserialize(payload)?,
+                )
+            },
+            OCPPCallAction::SignedFirmwareStatusNotification => {
+                OCPPCallResultPayload::SignedFirmwareStatusNotification(
+                    SignedFirmwareStatusNotificationResponse::deserialize(
+                        payload,
+                    )?,
+                )
+            },
+            OCPPCallAction::SignedUpdateFirmware => {
+                OCPPCallResultPayload::SignedUpdateFirmware(
+                    SignedUpdateFirmwareResponse::deserialize(payload)?,
+                )
+            },
             OCPPCallAction::StartTransaction => {
                 OCPPCallResultPayload::StartTransaction(
                     StartTransactionResponse::deserialize(payload)?,
@@ -644,17 +782,206 @@ impl<'de> Deserialize<'de> for OCPPCallError {
 }
 
 impl OCPPCallError {
-    fn from_call(
+    /// Create a call error in reply to the call with the given `unique_id`
+    pub fn new(
         unique_id: &str,
         error_code: OCPPCallErrorCode,
+        error_description: impl Into<String>,
+        error_details: serde_json::Value,
     ) -> OCPPCallError {
         OCPPCallError {
             unique_id: String::from(unique_id),
             error_code,
-            error_description: String::new(),
-            error_details: serde_json::json!({}),
+            error_description: error_description.into(),
+            error_details,
         }
     }
+
+    fn from_call(
+        unique_id: &str,
+        error_code: OCPPCallErrorCode,
+    ) -> OCPPCallError {
+        let error_description = error_code.description();
+        OCPPCallError::new(
+            unique_id,
+            error_code,
+            error_description,
+            serde_json::json!({}),
+        )
+    }
+
+    /// Create a call error from a failure to decode a call payload. Syntax
+    /// errors are reported as [OCPPCallErrorCode::FormationViolation]. Data
+    /// errors are told apart by the message serde gives them: a missing field
+    /// is an [OCPPCallErrorCode::OccurenceConstraintViolation], a field of the
+    /// wrong type a [OCPPCallErrorCode::TypeConstraintViolation] and any other
+    /// invalid value a [OCPPCallErrorCode::PropertyConstraintViolation].
+    pub fn from_serde_error(
+        unique_id: &str,
+        error: &serde_json::Error,
+    ) -> OCPPCallError {
+        use serde_json::error::Category;
+
+        // serde_json only exposes the kind of a data error through the
+        // wording of its message, which is not part of its stable API. The
+        // prefixes below are pinned by test_from_serde_error.
+        let message = error.to_string();
+        let error_code = match error.classify() {
+            Category::Io | Category::Syntax | Category::Eof => {
+                OCPPCallErrorCode::FormationViolation
+            },
+            Category::Data if message.starts_with("missing field") => {
+                OCPPCallErrorCode::OccurenceConstraintViolation
+            },
+            Category::Data if message.starts_with("invalid type") => {
+                OCPPCallErrorCode::TypeConstraintViolation
+            },
+            Category::Data => OCPPCallErrorCode::PropertyConstraintViolation,
+        };
+
+        OCPPCallError::new(
+            unique_id,
+            error_code,
+            message,
+            serde_json::json!({}),
+        )
+    }
+
+    /// Create a call error from a payload that failed schema validation. All
+    /// schema errors are listed in the details, the error code is taken from
+    /// the first of them.
+    pub fn from_validate_error(
+        unique_id: &str,
+        error: &ocpp_json_validate::JsonValidateError,
+    ) -> OCPPCallError {
+        let ocpp_json_validate::JsonValidateError::ValidationError(errors) =
+            error;
+
+        let error_code = match errors.first() {
+            Some(e) if e.ends_with("is a required property") => {
+                OCPPCallErrorCode::OccurenceConstraintViolation
+            },
+            Some(e) if e.contains("is not of type") => {
+                OCPPCallErrorCode::TypeConstraintViolation
+            },
+            _ => OCPPCallErrorCode::PropertyConstraintViolation,
+        };
+
+        OCPPCallError::new(
+            unique_id,
+            error_code,
+            errors.join("; "),
+            serde_json::json!({ "errors": errors }),
+        )
+    }
+}
+
+/// Failure to parse a frame with [parse_call]
+#[derive(Debug, Clone, Error)]
+pub enum ParseCallError {
+    /// The frame is a CallResult or CallError, which must not be answered
+    #[error("Frame with Message Type ID {0} is not a Call")]
+    NotACall(u64),
+    /// The frame is a broken call, answered with the contained error
+    #[error("Invalid call: {}", .0.error_description)]
+    Invalid(OCPPCallError),
+}
+
+/// Parse a raw text frame into a valid [OCPPCall]. A broken call is returned
+/// as [ParseCallError::Invalid] with an [OCPPCallError] ready to be sent back,
+/// so that it never goes unanswered. CallResult and CallError frames are
+/// returned as [ParseCallError::NotACall], as they must not get a reply.
+///
+/// When the unique id of the frame cannot be read, the error is addressed to
+/// unique id `"-1"`.
+///
+/// # Example
+/// ```
+/// use ocpp::*;
+///
+/// let Err(ParseCallError::Invalid(err)) = parse_call(r#"[2,"63:1","Authorize",{}]"#) else {
+///     panic!("Authorize without idTag should be invalid");
+/// };
+/// assert_eq!(err.unique_id, "63:1");
+/// assert!(matches!(err.error_code, OCPPCallErrorCode::OccurenceConstraintViolation));
+///
+/// let Err(ParseCallError::Invalid(err)) = parse_call(r#"[2,"63:2","FlyToTheMoon",{}]"#) else {
+///     panic!("Unknown action should be invalid");
+/// };
+/// assert!(matches!(err.error_code, OCPPCallErrorCode::NotImplemented));
+///
+/// let err = parse_call(r#"[3,"63:3",{}]"#).unwrap_err();
+/// assert!(matches!(err, ParseCallError::NotACall(3)));
+/// ```
+pub fn parse_call(text: &str) -> Result<OCPPCall, ParseCallError> {
+    const UNKNOWN_ID: &str = "-1";
+
+    let frame: serde_json::Value = serde_json::from_str(text).map_err(|e| {
+        ParseCallError::Invalid(OCPPCallError::from_serde_error(UNKNOWN_ID, &e))
+    })?;
+
+    if let Some(message_type_id @ (3 | 4)) =
+        frame.get(0).and_then(serde_json::Value::as_u64)
+    {
+        return Err(ParseCallError::NotACall(message_type_id));
+    }
+
+    let unique_id = frame
+        .get(1)
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or(UNKNOWN_ID)
+        .to_owned();
+    let formation_violation = |description: &str| {
+        ParseCallError::Invalid(OCPPCallError::new(
+            &unique_id,
+            OCPPCallErrorCode::FormationViolation,
+            description,
+            serde_json::json!({ "frame": text }),
+        ))
+    };
+
+    let fields = match frame.as_array() {
+        Some(fields) if fields.len() == 4 => fields,
+        _ => {
+            return Err(formation_violation(
+                "Call should be an array of 4 elements",
+            ))
+        },
+    };
+    if fields[0] != 2 {
+        return Err(formation_violation(
+            "Message Type ID for Call should be '2'",
+        ));
+    }
+    if !fields[1].is_string() {
+        return Err(formation_violation("Unique id should be a string"));
+    }
+    let action = match fields[2].as_str() {
+        Some(action) => action.to_owned(),
+        None => return Err(formation_violation("Action should be a string")),
+    };
+    if action.parse::<OCPPCallAction>().is_err() {
+        return Err(ParseCallError::Invalid(OCPPCallError::new(
+            &unique_id,
+            OCPPCallErrorCode::NotImplemented,
+            format!("Unknown action {}", action),
+            serde_json::json!({ "action": action }),
+        )));
+    }
+    if !fields[3].is_object() {
+        return Err(formation_violation("Payload should be an object"));
+    }
+
+    let call = OCPPCall::deserialize(frame).map_err(|e| {
+        ParseCallError::Invalid(OCPPCallError::from_serde_error(&unique_id, &e))
+    })?;
+    call.payload.validate().map_err(|e| {
+        ParseCallError::Invalid(OCPPCallError::from_validate_error(
+            &unique_id, &e,
+        ))
+    })?;
+
+    Ok(call)
 }
 
 /// OCPP Call Error Code
@@ -679,6 +1006,44 @@ impl std::fmt::Display for OCPPCallErrorCode {
     }
 }
 
+impl OCPPCallErrorCode {
+    /// Description of the error code as given by the OCPP-J specification
+    pub fn description(&self) -> &'static str {
+        match self {
+            OCPPCallErrorCode::NotImplemented => {
+                "Requested Action is not known by receiver"
+            },
+            OCPPCallErrorCode::NotSupported => {
+                "Requested Action is recognized but not supported by the receiver"
+            },
+            OCPPCallErrorCode::InternalError => {
+                "An internal error occurred and the receiver was not able to process the requested Action successfully"
+            },
+            OCPPCallErrorCode::ProtocolError => {
+                "Payload for Action is incomplete"
+            },
+            OCPPCallErrorCode::SecurityError => {
+                "During the processing of Action a security issue occurred preventing receiver from completing the Action successfully"
+            },
+            OCPPCallErrorCode::FormationViolation => {
+                "Payload for Action is syntactically incorrect or not conform the PDU structure for Action"
+            },
+            OCPPCallErrorCode::PropertyConstraintViolation => {
+                "Payload is syntactically correct but at least one field contains an invalid value"
+            },
+            OCPPCallErrorCode::OccurenceConstraintViolation => {
+                "Payload for Action is syntactically correct but at least one of the fields violates occurence constraints"
+            },
+            OCPPCallErrorCode::TypeConstraintViolation => {
+                "Payload for Action is syntactically correct but at least one of the fields violates data type constraints"
+            },
+            OCPPCallErrorCode::GenericError => {
+                "Any other error not covered by the previous ones"
+            },
+        }
+    }
+}
+
 /// OCPP Call Types
 #[non_exhaustive]
 #[allow(missing_docs)]
@@ -687,24 +1052,37 @@ impl std::fmt::Display for OCPPCallErrorCode {
 pub enum OCPPCallPayload {
     Authorize(AuthorizeRequest),
     BootNotification(BootNotificationRequest),
+    CancelReservation(CancelReservationRequest),
+    CertificateSigned(CertificateSignedRequest),
     ChangeAvailability(ChangeAvailabilityRequest),
     ChangeConfiguration(ChangeConfigurationRequest),
     ClearCache(ClearCacheRequest),
     ClearChargingProfile(ClearChargingProfileRequest),
     DataTransfer(DataTransferRequest),
+    DeleteCertificate(DeleteCertificateRequest),
     DiagnosticsStatusNotification(DiagnosticsStatusNotificationRequest),
+    ExtendedTriggerMessage(ExtendedTriggerMessageRequest),
     FirmwareStatusNotification(FirmwareStatusNotificationRequest),
     GetCompositeSchedule(GetCompositeScheduleRequest),
     GetConfiguration(GetConfigurationRequest),
     GetDiagnostics(GetDiagnosticsRequest),
+    GetInstalledCertificateIds(GetInstalledCertificateIdsRequest),
     GetLocalListVersion(GetLocalListVersionRequest),
+    GetLog(GetLogRequest),
     Heartbeat(HeartbeatRequest),
+    InstallCertificate(InstallCertificateRequest),
+    LogStatusNotification(LogStatusNotificationRequest),
     MeterValues(MeterValuesRequest),
     RemoteStartTransaction(RemoteStartTransactionRequest),
     RemoteStopTransaction(RemoteStopTransactionRequest),
+    ReserveNow(ReserveNowRequest),
     Reset(ResetRequest),
+    SecurityEventNotification(SecurityEventNotificationRequest),
     SendLocalList(SendLocalListRequest),
     SetChargingProfile(SetChargingProfileRequest),
+    SignCertificate(SignCertificateRequest),
+    SignedFirmwareStatusNotification(SignedFirmwareStatusNotificationRequest),
+    SignedUpdateFirmware(SignedUpdateFirmwareRequest),
     StartTransaction(StartTransactionRequest),
     StatusNotification(StatusNotificationRequest),
     StopTransaction(StopTransactionRequest),
@@ -719,113 +1097,7 @@ impl OCPPCallPayload {
         &self,
         result: OCPPCallResultUnknown,
     ) -> Result<OCPPCallResultPayload, serde_json::error::Error> {
-        use OCPPCallPayload::*;
-
-        let output = match self {
-            Authorize(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::Authorize,
-                result,
-            )?,
-            BootNotification(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::BootNotification,
-                result,
-            )?,
-            ChangeAvailability(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::ChangeAvailability,
-                result,
-            )?,
-            ChangeConfiguration(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::ChangeConfiguration,
-                result,
-            )?,
-            ClearCache(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::ClearCache,
-                result,
-            )?,
-            ClearChargingProfile(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::ClearChargingProfile,
-                result,
-            )?,
-            DataTransfer(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::DataTransfer,
-                result,
-            )?,
-            DiagnosticsStatusNotification(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::DiagnosticsStatusNotification,
-                result,
-            )?,
-            FirmwareStatusNotification(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::FirmwareStatusNotification,
-                result,
-            )?,
-            GetCompositeSchedule(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::GetCompositeSchedule,
-                result,
-            )?,
-            GetConfiguration(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::GetConfiguration,
-                result,
-            )?,
-            GetDiagnostics(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::GetDiagnostics,
-                result,
-            )?,
-            GetLocalListVersion(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::GetLocalListVersion,
-                result,
-            )?,
-            Heartbeat(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::Heartbeat,
-                result,
-            )?,
-            MeterValues(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::MeterValues,
-                result,
-            )?,
-            RemoteStartTransaction(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::RemoteStartTransaction,
-                result,
-            )?,
-            RemoteStopTransaction(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::RemoteStopTransaction,
-                result,
-            )?,
-            Reset(_) => {
-                OCPPCallResult::from_unknown(&OCPPCallAction::Reset, result)?
-            },
-            SendLocalList(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::SendLocalList,
-                result,
-            )?,
-            SetChargingProfile(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::SetChargingProfile,
-                result,
-            )?,
-            StartTransaction(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::StartTransaction,
-                result,
-            )?,
-            StatusNotification(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::StatusNotification,
-                result,
-            )?,
-            StopTransaction(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::StopTransaction,
-                result,
-            )?,
-            TriggerMessage(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::TriggerMessage,
-                result,
-            )?,
-            UnlockConnector(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::UnlockConnector,
-                result,
-            )?,
-            UpdateFirmware(_) => OCPPCallResult::from_unknown(
-                &OCPPCallAction::UpdateFirmware,
-                result,
-            )?,
-        };
+        let output = OCPPCallResult::from_unknown(&self.action(), result)?;
 
         Ok(output.payload)
     }
@@ -836,26 +1108,41 @@ impl ocpp_json_validate::JsonValidate for OCPPCallPayload {
         match self {
             OCPPCallPayload::Authorize(req) => req.validate(),
             OCPPCallPayload::BootNotification(req) => req.validate(),
+            OCPPCallPayload::CancelReservation(req) => req.validate(),
+            OCPPCallPayload::CertificateSigned(req) => req.validate(),
             OCPPCallPayload::ChangeAvailability(req) => req.validate(),
             OCPPCallPayload::ChangeConfiguration(req) => req.validate(),
             OCPPCallPayload::ClearCache(req) => req.validate(),
             OCPPCallPayload::ClearChargingProfile(req) => req.validate(),
             OCPPCallPayload::DataTransfer(req) => req.validate(),
+            OCPPCallPayload::DeleteCertificate(req) => req.validate(),
             OCPPCallPayload::DiagnosticsStatusNotification(req) => {
                 req.validate()
             },
+            OCPPCallPayload::ExtendedTriggerMessage(req) => req.validate(),
             OCPPCallPayload::FirmwareStatusNotification(req) => req.validate(),
             OCPPCallPayload::GetCompositeSchedule(req) => req.validate(),
             OCPPCallPayload::GetConfiguration(req) => req.validate(),
             OCPPCallPayload::GetDiagnostics(req) => req.validate(),
+            OCPPCallPayload::GetInstalledCertificateIds(req) => req.validate(),
             OCPPCallPayload::GetLocalListVersion(req) => req.validate(),
+            OCPPCallPayload::GetLog(req) => req.validate(),
             OCPPCallPayload::Heartbeat(req) => req.validate(),
+            OCPPCallPayload::InstallCertificate(req) => req.validate(),
+            OCPPCallPayload::LogStatusNotification(req) => req.validate(),
             OCPPCallPayload::MeterValues(req) => req.validate(),
             OCPPCallPayload::RemoteStartTransaction(req) => req.validate(),
             OCPPCallPayload::RemoteStopTransaction(req) => req.validate(),
+            OCPPCallPayload::ReserveNow(req) => req.validate(),
             OCPPCallPayload::Reset(req) => req.validate(),
+            OCPPCallPayload::SecurityEventNotification(req) => req.validate(),
             OCPPCallPayload::SendLocalList(req) => req.validate(),
             OCPPCallPayload::SetChargingProfile(req) => req.validate(),
+            OCPPCallPayload::SignCertificate(req) => req.validate(),
+            OCPPCallPayload::SignedFirmwareStatusNotification(req) => {
+                req.validate()
+            },
+            OCPPCallPayload::SignedUpdateFirmware(req) => req.validate(),
             OCPPCallPayload::StartTransaction(req) => req.validate(),
             OCPPCallPayload::StatusNotification(req) => req.validate(),
             OCPPCallPayload::StopTransaction(req) => req.validate(),
@@ -874,24 +1161,37 @@ impl ocpp_json_validate::JsonValidate for OCPPCallPayload {
 pub enum OCPPCallResultPayload {
     Authorize(AuthorizeResponse),
     BootNotification(BootNotificationResponse),
+    CancelReservation(CancelReservationResponse),
+    CertificateSigned(CertificateSignedResponse),
     ChangeAvailability(ChangeAvailabilityResponse),
     ChangeConfiguration(ChangeConfigurationResponse),
     ClearCache(ClearCacheResponse),
     ClearChargingProfile(ClearChargingProfileResponse),
     DataTransfer(DataTransferResponse),
+    DeleteCertificate(DeleteCertificateResponse),
     DiagnosticsStatusNotification(DiagnosticsStatusNotificationResponse),
+    ExtendedTriggerMessage(ExtendedTriggerMessageResponse),
     FirmwareStatusNotification(FirmwareStatusNotificationResponse),
     GetCompositeSchedule(GetCompositeScheduleResponse),
     GetConfiguration(GetConfigurationResponse),
     GetDiagnostics(GetDiagnosticsResponse),
+    GetInstalledCertificateIds(GetInstalledCertificateIdsResponse),
     GetLocalListVersion(GetLocalListVersionResponse),
+    GetLog(GetLogResponse),
     Heartbeat(HeartbeatResponse),
+    InstallCertificate(InstallCertificateResponse),
+    LogStatusNotification(LogStatusNotificationResponse),
     MeterValues(MeterValuesResponse),
     RemoteStartTransaction(RemoteStartTransactionResponse),
     RemoteStopTransaction(RemoteStopTransactionResponse),
+    ReserveNow(ReserveNowResponse),
     Reset(ResetResponse),
+    SecurityEventNotification(SecurityEventNotificationResponse),
     SendLocalList(SendLocalListResponse),
     SetChargingProfile(SetChargingProfileResponse),
+    SignCertificate(SignCertificateResponse),
+    SignedFirmwareStatusNotification(SignedFirmwareStatusNotificationResponse),
+    SignedUpdateFirmware(SignedUpdateFirmwareResponse),
     StartTransaction(StartTransactionResponse),
     StatusNotification(StatusNotificationResponse),
     StopTransaction(StopTransactionResponse),
@@ -905,28 +1205,45 @@ impl ocpp_json_validate::JsonValidate for OCPPCallResultPayload {
         match self {
             OCPPCallResultPayload::Authorize(r) => r.validate(),
             OCPPCallResultPayload::BootNotification(r) => r.validate(),
+            OCPPCallResultPayload::CancelReservation(r) => r.validate(),
+            OCPPCallResultPayload::CertificateSigned(r) => r.validate(),
             OCPPCallResultPayload::ChangeAvailability(r) => r.validate(),
             OCPPCallResultPayload::ChangeConfiguration(r) => r.validate(),
             OCPPCallResultPayload::ClearCache(r) => r.validate(),
             OCPPCallResultPayload::ClearChargingProfile(r) => r.validate(),
             OCPPCallResultPayload::DataTransfer(r) => r.validate(),
+            OCPPCallResultPayload::DeleteCertificate(r) => r.validate(),
             OCPPCallResultPayload::DiagnosticsStatusNotification(r) => {
                 r.validate()
             },
+            OCPPCallResultPayload::ExtendedTriggerMessage(r) => r.validate(),
             OCPPCallResultPayload::FirmwareStatusNotification(r) => {
                 r.validate()
             },
             OCPPCallResultPayload::GetCompositeSchedule(r) => r.validate(),
             OCPPCallResultPayload::GetConfiguration(r) => r.validate(),
             OCPPCallResultPayload::GetDiagnostics(r) => r.validate(),
+            OCPPCallResultPayload::GetInstalledCertificateIds(r) => {
+                r.validate()
+            },
             OCPPCallResultPayload::GetLocalListVersion(r) => r.validate(),
+            OCPPCallResultPayload::GetLog(r) => r.validate(),
             OCPPCallResultPayload::Heartbeat(r) => r.validate(),
+            OCPPCallResultPayload::InstallCertificate(r) => r.validate(),
+            OCPPCallResultPayload::LogStatusNotification(r) => r.validate(),
             OCPPCallResultPayload::MeterValues(r) => r.validate(),
             OCPPCallResultPayload::RemoteStartTransaction(r) => r.validate(),
             OCPPCallResultPayload::RemoteStopTransaction(r) => r.validate(),
+            OCPPCallResultPayload::ReserveNow(r) => r.validate(),
             OCPPCallResultPayload::Reset(r) => r.validate(),
+            OCPPCallResultPayload::SecurityEventNotification(r) => r.validate(),
             OCPPCallResultPayload::SendLocalList(r) => r.validate(),
             OCPPCallResultPayload::SetChargingProfile(r) => r.validate(),
+            OCPPCallResultPayload::SignCertificate(r) => r.validate(),
+            OCPPCallResultPayload::SignedFirmwareStatusNotification(r) => {
+                r.validate()
+            },
+            OCPPCallResultPayload::SignedUpdateFirmware(r) => r.validate(),
             OCPPCallResultPayload::StartTransaction(r) => r.validate(),
             OCPPCallResultPayload::StatusNotification(r) => r.validate(),
             OCPPCallResultPayload::StopTransaction(r) => r.validate(),
@@ -940,28 +1257,41 @@ impl ocpp_json_validate::JsonValidate for OCPPCallResultPayload {
 /// OCPP Call Types
 #[non_exhaustive]
 #[allow(missing_docs)]
-#[derive(Debug, Display, PartialEq, Clone)]
+#[derive(Debug, Display, EnumString, PartialEq, Clone)]
 pub enum OCPPCallAction {
     Authorize,
     BootNotification,
+    CancelReservation,
+    CertificateSigned,
     ChangeAvailability,
     ChangeConfiguration,
     ClearCache,
     ClearChargingProfile,
     DataTransfer,
+    DeleteCertificate,
     DiagnosticsStatusNotification,
+    ExtendedTriggerMessage,
     FirmwareStatusNotification,
     GetCompositeSchedule,
     GetConfiguration,
     GetDiagnostics,
+    GetInstalledCertificateIds,
     GetLocalListVersion,
+    GetLog,
     Heartbeat,
+    InstallCertificate,
+    LogStatusNotification,
     MeterValues,
     RemoteStartTransaction,
     RemoteStopTransaction,
+    ReserveNow,
     Reset,
+    SecurityEventNotification,
     SendLocalList,
     SetChargingProfile,
+    SignCertificate,
+    SignedFirmwareStatusNotification,
+    SignedUpdateFirmware,
     StartTransaction,
     StatusNotification,
     StopTransaction,
@@ -970,6 +1300,168 @@ pub enum OCPPCallAction {
     UpdateFirmware,
 }
 
+/// Request type of an OCPP call, paired with its [OCPPCallAction] and
+/// response type. Implemented by every `*Request` type, so generic code can
+/// send a request and decode its response with compile-time type safety.
+///
+/// # Example
+/// ```
+/// # fn ocpp_request_example() -> Result<(), serde_json::Error> {
+/// use ocpp::*;
+///
+/// fn decode<R: OCPPRequest>(
+///     unknown: OCPPCallResultUnknown,
+/// ) -> Result<R::Response, serde_json::Error> {
+///     serde_json::from_value(unknown.payload)
+/// }
+///
+/// let call: OCPPCall = (String::from("63:1"), HeartbeatRequest {}.into()).into();
+/// assert_eq!(call.payload.action(), HeartbeatRequest::ACTION);
+///
+/// let json = r#"[3,"63:1",{"currentTime":"2022-01-24T04:30:50.621Z"}]"#;
+/// let unknown: OCPPCallResultUnknown = serde_json::from_str(json)?;
+/// let res: HeartbeatResponse = decode::<HeartbeatRequest>(unknown)?;
+/// println!("Heartbeat at {}", res.current_time);
+/// # Ok(())
+/// # }
+/// ```
+pub trait OCPPRequest:
+    Serialize
+    + de::DeserializeOwned
+    + JsonValidate
+    + Into<OCPPCallPayload>
+    + std::fmt::Debug
+    + Clone
+    + Send
+    + 'static
+{
+    /// Action of the call carrying this request
+    const ACTION: OCPPCallAction;
+    /// Response type expected in reply to this request
+    type Response: OCPPResponse<Request = Self>;
+}
+
+/// Response type of an OCPP call, paired with its request type. Implemented
+/// by every `*Response` type.
+pub trait OCPPResponse:
+    Serialize
+    + de::DeserializeOwned
+    + JsonValidate
+    + Into<OCPPCallResultPayload>
+    + TryFrom<OCPPCallResultPayload, Error = OCPPCallResultPayload>
+    + std::fmt::Debug
+    + Clone
+    + Send
+    + 'static
+{
+    /// Request type this is a response to
+    type Request: OCPPRequest<Response = Self>;
+}
+
+/// Invokes the macro `$callback` with every action, its request and response
+/// types, the name of the method handling it and the side sending it, one of
+/// `ChargePoint`, `CentralSystem` or `Both`:
+/// `Action(ActionRequest, ActionResponse) action: Sender,`
+macro_rules! ocpp_actions {
+    ($callback:ident) => {
+        $callback! {
+            Authorize(AuthorizeRequest, AuthorizeResponse) authorize: ChargePoint,
+            BootNotification(BootNotificationRequest, BootNotificationResponse) boot_notification: ChargePoint,
+            CancelReservation(CancelReservationRequest, CancelReservationResponse) cancel_reservation: CentralSystem,
+            CertificateSigned(CertificateSignedRequest, CertificateSignedResponse) certificate_signed: CentralSystem,
+            ChangeAvailability(ChangeAvailabilityRequest, ChangeAvailabilityResponse) change_availability: CentralSystem,
+            ChangeConfiguration(ChangeConfigurationRequest, ChangeConfigurationResponse) change_configuration: CentralSystem,
+            ClearCache(ClearCacheRequest, ClearCacheResponse) clear_cache: CentralSystem,
+            ClearChargingProfile(ClearChargingProfileRequest, ClearChargingProfileResponse) clear_charging_profile: CentralSystem,
+            DataTransfer(DataTransferRequest, DataTransferResponse) data_transfer: Both,
+            DeleteCertificate(DeleteCertificateRequest, DeleteCertificateResponse) delete_certificate: CentralSystem,
+            DiagnosticsStatusNotification(DiagnosticsStatusNotificationRequest, DiagnosticsStatusNotificationResponse) diagnostics_status_notification: ChargePoint,
+            ExtendedTriggerMessage(ExtendedTriggerMessageRequest, ExtendedTriggerMessageResponse) extended_trigger_message: CentralSystem,
+            FirmwareStatusNotification(FirmwareStatusNotificationRequest, FirmwareStatusNotificationResponse) firmware_status_notification: ChargePoint,
+            GetCompositeSchedule(GetCompositeScheduleRequest, GetCompositeScheduleResponse) get_composite_schedule: CentralSystem,
+            GetConfiguration(GetConfigurationRequest, GetConfigurationResponse) get_configuration: CentralSystem,
+            GetDiagnostics(GetDiagnosticsRequest, GetDiagnosticsResponse) get_diagnostics: CentralSystem,
+            GetInstalledCertificateIds(GetInstalledCertificateIdsRequest, GetInstalledCertificateIdsResponse) get_installed_certificate_ids: CentralSystem,
+            GetLocalListVersion(GetLocalListVersionRequest, GetLocalListVersionResponse) get_local_list_version: CentralSystem,
+            GetLog(GetLogRequest, GetLogResponse) get_log: CentralSystem,
+            Heartbeat(HeartbeatRequest, HeartbeatResponse) heartbeat: ChargePoint,
+            InstallCertificate(InstallCertificateRequest, InstallCertificateResponse) install_certificate: CentralSystem,
+            LogStatusNotification(LogStatusNotificationRequest, LogStatusNotificationResponse) log_status_notification: ChargePoint,
+            MeterValues(MeterValuesRequest, MeterValuesResponse) meter_values: ChargePoint,
+            RemoteStartTransaction(RemoteStartTransactionRequest, RemoteStartTransactionResponse) remote_start_transaction: CentralSystem,
+            RemoteStopTransaction(RemoteStopTransactionRequest, RemoteStopTransactionResponse) remote_stop_transaction: CentralSystem,
+            ReserveNow(ReserveNowRequest, ReserveNowResponse) reserve_now: CentralSystem,
+            Reset(ResetRequest, ResetResponse) reset: CentralSystem,
+            SecurityEventNotification(SecurityEventNotificationRequest, SecurityEventNotificationResponse) security_event_notification: ChargePoint,
+            SendLocalList(SendLocalListRequest, SendLocalListResponse) send_local_list: CentralSystem,
+            SetChargingProfile(SetChargingProfileRequest, SetChargingProfileResponse) set_charging_profile: CentralSystem,
+            SignCertificate(SignCertificateRequest, SignCertificateResponse) sign_certificate: ChargePoint,
+            SignedFirmwareStatusNotification(SignedFirmwareStatusNotificationRequest, SignedFirmwareStatusNotificationResponse) signed_firmware_status_notification: ChargePoint,
+            SignedUpdateFirmware(SignedUpdateFirmwareRequest, SignedUpdateFirmwareResponse) signed_update_firmware: CentralSystem,
+            StartTransaction(StartTransactionRequest, StartTransactionResponse) start_transaction: ChargePoint,
+            StatusNotification(StatusNotificationRequest, StatusNotificationResponse) status_notification: ChargePoint,
+            StopTransaction(StopTransactionRequest, StopTransactionResponse) stop_transaction: ChargePoint,
+            TriggerMessage(TriggerMessageRequest, TriggerMessageResponse) trigger_message: CentralSystem,
+            UnlockConnector(UnlockConnectorRequest, UnlockConnectorResponse) unlock_connector: CentralSystem,
+            UpdateFirmware(UpdateFirmwareRequest, UpdateFirmwareResponse) update_firmware: CentralSystem,
+        }
+    };
+}
+pub(crate) use ocpp_actions;
+
+/// Implements [OCPPRequest], [OCPPResponse] and the payload conversions for
+/// each action
+macro_rules! impl_ocpp_actions {
+    ($($action:ident($req:ident, $res:ident) $method:ident: $sender:ident,)*) => {
+        $(
+            impl OCPPRequest for $req {
+                const ACTION: OCPPCallAction = OCPPCallAction::$action;
+                type Response = $res;
+            }
+
+            impl OCPPResponse for $res {
+                type Request = $req;
+            }
+
+            impl From<$req> for OCPPCallPayload {
+                fn from(req: $req) -> Self {
+                    OCPPCallPayload::$action(req)
+                }
+            }
+
+            impl From<$res> for OCPPCallResultPayload {
+                fn from(res: $res) -> Self {
+                    OCPPCallResultPayload::$action(res)
+                }
+            }
+
+            impl TryFrom<OCPPCallResultPayload> for $res {
+                type Error = OCPPCallResultPayload;
+
+                fn try_from(
+                    payload: OCPPCallResultPayload,
+                ) -> Result<Self, Self::Error> {
+                    match payload {
+                        OCPPCallResultPayload::$action(res) => Ok(res),
+                        other => Err(other),
+                    }
+                }
+            }
+        )*
+
+        impl OCPPCallPayload {
+            /// Get the [OCPPCallAction] of this payload
+            pub fn action(&self) -> OCPPCallAction {
+                match self {
+                    $(OCPPCallPayload::$action(_) => OCPPCallAction::$action,)*
+                }
+            }
+        }
+    };
+}
+
+ocpp_actions!(impl_ocpp_actions);
+
 /// Predefined methods to respond to an OCPP request. Each call type has its
 /// own method, and is expected to either return a valid response or an error
 /// code. OCPPCallResultBuilder may be passed an OCPPCall via the [build](OCPPCallResultBuilder::build)
@@ -1048,6 +1540,20 @@ pub trait OCPPCallResultBuilder {
     ) -> Result<BootNotificationResponse, OCPPCallErrorCode> {
         Err(OCPPCallErrorCode::NotImplemented)
     }
+    /// Handle CancelReservationRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn cancel_reservation(
+        &mut self,
+        _req: CancelReservationRequest,
+    ) -> Result<CancelReservationResponse, OCPPCallErrorCode> {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
+    /// Handle CertificateSignedRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn certificate_signed(
+        &mut self,
+        _req: CertificateSignedRequest,
+    ) -> Result<CertificateSignedResponse, OCPPCallErrorCode> {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
     /// Handle ChangeAvailabilityRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
     fn change_availability(
         &mut self,
@@ -1083,6 +1589,13 @@ pub trait OCPPCallResultBuilder {
     ) -> Result<DataTransferResponse, OCPPCallErrorCode> {
         Err(OCPPCallErrorCode::NotImplemented)
     }
+    /// Handle DeleteCertificateRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn delete_certificate(
+        &mut self,
+        _req: DeleteCertificateRequest,
+    ) -> Result<DeleteCertificateResponse, OCPPCallErrorCode> {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
     /// Handle DiagnosticsStatusNotificationRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
     fn diagnostics_status_notification(
         &mut self,
@@ -1090,6 +1603,13 @@ pub trait OCPPCallResultBuilder {
     ) -> Result<DiagnosticsStatusNotificationResponse, OCPPCallErrorCode> {
         Err(OCPPCallErrorCode::NotImplemented)
     }
+    /// Handle ExtendedTriggerMessageRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn extended_trigger_message(
+        &mut self,
+        _req: ExtendedTriggerMessageRequest,
+    ) -> Result<ExtendedTriggerMessageResponse, OCPPCallErrorCode> {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
     /// Handle FirmwareStatusNotificationRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
     fn firmware_status_notification(
         &mut self,
@@ -1118,6 +1638,13 @@ pub trait OCPPCallResultBuilder {
     ) -> Result<GetDiagnosticsResponse, OCPPCallErrorCode> {
         Err(OCPPCallErrorCode::NotImplemented)
     }
+    /// Handle GetInstalledCertificateIdsRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn get_installed_certificate_ids(
+        &mut self,
+        _req: GetInstalledCertificateIdsRequest,
+    ) -> Result<GetInstalledCertificateIdsResponse, OCPPCallErrorCode> {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
     /// Handle GetLocalListVersionRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
     fn get_local_list_version(
         &mut self,
@@ -1125,6 +1652,13 @@ pub trait OCPPCallResultBuilder {
     ) -> Result<GetLocalListVersionResponse, OCPPCallErrorCode> {
         Err(OCPPCallErrorCode::NotImplemented)
     }
+    /// Handle GetLogRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn get_log(
+        &mut self,
+        _req: GetLogRequest,
+    ) -> Result<GetLogResponse, OCPPCallErrorCode> {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
     /// Handle HeartbeatRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
     fn heartbeat(
         &mut self,
@@ -1132,6 +1666,20 @@ pub trait OCPPCallResultBuilder {
     ) -> Result<HeartbeatResponse, OCPPCallErrorCode> {
         Err(OCPPCallErrorCode::NotImplemented)
     }
+    /// Handle InstallCertificateRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn install_certificate(
+        &mut self,
+        _req: InstallCertificateRequest,
+    ) -> Result<InstallCertificateResponse, OCPPCallErrorCode> {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
+    /// Handle LogStatusNotificationRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn log_status_notification(
+        &mut self,
+        _req: LogStatusNotificationRequest,
+    ) -> Result<LogStatusNotificationResponse, OCPPCallErrorCode> {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
     /// Handle MeterValuesRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
     fn meter_values(
         &mut self,
@@ -1153,6 +1701,13 @@ pub trait OCPPCallResultBuilder {
     ) -> Result<RemoteStopTransactionResponse, OCPPCallErrorCode> {
         Err(OCPPCallErrorCode::NotImplemented)
     }
+    /// Handle ReserveNowRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn reserve_now(
+        &mut self,
+        _req: ReserveNowRequest,
+    ) -> Result<ReserveNowResponse, OCPPCallErrorCode> {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
     /// Handle ResetRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
     fn reset(
         &mut self,
@@ -1160,6 +1715,13 @@ pub trait OCPPCallResultBuilder {
     ) -> Result<ResetResponse, OCPPCallErrorCode> {
         Err(OCPPCallErrorCode::NotImplemented)
     }
+    /// Handle SecurityEventNotificationRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn security_event_notification(
+        &mut self,
+        _req: SecurityEventNotificationRequest,
+    ) -> Result<SecurityEventNotificationResponse, OCPPCallErrorCode> {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
     /// Handle SendLocalListRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
     fn send_local_list(
         &mut self,
@@ -1174,6 +1736,28 @@ pub trait OCPPCallResultBuilder {
     ) -> Result<SetChargingProfileResponse, OCPPCallErrorCode> {
         Err(OCPPCallErrorCode::NotImplemented)
     }
+    /// Handle SignCertificateRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn sign_certificate(
+        &mut self,
+        _req: SignCertificateRequest,
+    ) -> Result<SignCertificateResponse, OCPPCallErrorCode> {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
+    /// Handle SignedFirmwareStatusNotificationRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn signed_firmware_status_notification(
+        &mut self,
+        _req: SignedFirmwareStatusNotificationRequest,
+    ) -> Result<SignedFirmwareStatusNotificationResponse, OCPPCallErrorCode>
+    {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
+    /// Handle SignedUpdateFirmwareRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
+    fn signed_update_firmware(
+        &mut self,
+        _req: SignedUpdateFirmwareRequest,
+    ) -> Result<SignedUpdateFirmwareResponse, OCPPCallErrorCode> {
+        Err(OCPPCallErrorCode::NotImplemented)
+    }
     /// Handle StartTransactionRequest. Returns [OCPPCallErrorCode::NotImplemented] by default.
     fn start_transaction(
         &mut self,
@@ -1229,10 +1813,7 @@ pub trait OCPPCallResultBuilder {
         // Validate incoming payload
         if let Err(e) = payload.validate() {
             tracing::warn!("OCPP Request Invalid: {:?}", e);
-            return Err(OCPPCallError::from_call(
-                &unique_id,
-                OCPPCallErrorCode::ProtocolError,
-            ));
+            return Err(OCPPCallError::from_validate_error(&unique_id, &e));
         }
 
         let payload = match payload {
@@ -1244,6 +1825,14 @@ pub trait OCPPCallResultBuilder {
                 .boot_notification(req)
                 .map(OCPPCallResultPayload::BootNotification)
                 .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::CancelReservation(req) => self
+                .cancel_reservation(req)
+                .map(OCPPCallResultPayload::CancelReservation)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::CertificateSigned(req) => self
+                .certificate_signed(req)
+                .map(OCPPCallResultPayload::CertificateSigned)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
             OCPPCallPayload::ChangeAvailability(req) => self
                 .change_availability(req)
                 .map(OCPPCallResultPayload::ChangeAvailability)
@@ -1264,10 +1853,18 @@ pub trait OCPPCallResultBuilder {
                 .data_transfer(req)
                 .map(OCPPCallResultPayload::DataTransfer)
                 .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::DeleteCertificate(req) => self
+                .delete_certificate(req)
+                .map(OCPPCallResultPayload::DeleteCertificate)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
             OCPPCallPayload::DiagnosticsStatusNotification(req) => self
                 .diagnostics_status_notification(req)
                 .map(OCPPCallResultPayload::DiagnosticsStatusNotification)
                 .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::ExtendedTriggerMessage(req) => self
+                .extended_trigger_message(req)
+                .map(OCPPCallResultPayload::ExtendedTriggerMessage)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
             OCPPCallPayload::FirmwareStatusNotification(req) => self
                 .firmware_status_notification(req)
                 .map(OCPPCallResultPayload::FirmwareStatusNotification)
@@ -1284,14 +1881,30 @@ pub trait OCPPCallResultBuilder {
                 .get_diagnostics(req)
                 .map(OCPPCallResultPayload::GetDiagnostics)
                 .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::GetInstalledCertificateIds(req) => self
+                .get_installed_certificate_ids(req)
+                .map(OCPPCallResultPayload::GetInstalledCertificateIds)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
             OCPPCallPayload::GetLocalListVersion(req) => self
                 .get_local_list_version(req)
                 .map(OCPPCallResultPayload::GetLocalListVersion)
                 .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::GetLog(req) => self
+                .get_log(req)
+                .map(OCPPCallResultPayload::GetLog)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
             OCPPCallPayload::Heartbeat(req) => self
                 .heartbeat(req)
                 .map(OCPPCallResultPayload::Heartbeat)
                 .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::InstallCertificate(req) => self
+                .install_certificate(req)
+                .map(OCPPCallResultPayload::InstallCertificate)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::LogStatusNotification(req) => self
+                .log_status_notification(req)
+                .map(OCPPCallResultPayload::LogStatusNotification)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
             OCPPCallPayload::MeterValues(req) => self
                 .meter_values(req)
                 .map(OCPPCallResultPayload::MeterValues)
@@ -1304,10 +1917,18 @@ pub trait OCPPCallResultBuilder {
                 .remote_stop_transaction(req)
                 .map(OCPPCallResultPayload::RemoteStopTransaction)
                 .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::ReserveNow(req) => self
+                .reserve_now(req)
+                .map(OCPPCallResultPayload::ReserveNow)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
             OCPPCallPayload::Reset(req) => self
                 .reset(req)
                 .map(OCPPCallResultPayload::Reset)
                 .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::SecurityEventNotification(req) => self
+                .security_event_notification(req)
+                .map(OCPPCallResultPayload::SecurityEventNotification)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
             OCPPCallPayload::SendLocalList(req) => self
                 .send_local_list(req)
                 .map(OCPPCallResultPayload::SendLocalList)
@@ -1316,6 +1937,18 @@ pub trait OCPPCallResultBuilder {
                 .set_charging_profile(req)
                 .map(OCPPCallResultPayload::SetChargingProfile)
                 .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::SignCertificate(req) => self
+                .sign_certificate(req)
+                .map(OCPPCallResultPayload::SignCertificate)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::SignedFirmwareStatusNotification(req) => self
+                .signed_firmware_status_notification(req)
+                .map(OCPPCallResultPayload::SignedFirmwareStatusNotification)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
+            OCPPCallPayload::SignedUpdateFirmware(req) => self
+                .signed_update_firmware(req)
+                .map(OCPPCallResultPayload::SignedUpdateFirmware)
+                .map_err(|e| OCPPCallError::from_call(&unique_id, e))?,
             OCPPCallPayload::StartTransaction(req) => self
                 .start_transaction(req)
                 .map(OCPPCallResultPayload::StartTransaction)
@@ -1354,3 +1987,99 @@ pub trait OCPPCallResultBuilder {
         Ok(OCPPCallResult { unique_id, payload })
     }
 }
+
+/// Declares [AsyncOCPPCallResultBuilder] with a handler method per action
+macro_rules! async_call_result_builder {
+    ($($action:ident($req:ident, $res:ident) $method:ident: $sender:ident,)*) => {
+        /// Asynchronous counterpart of [OCPPCallResultBuilder], for handlers that
+        /// need to await a database or another service while building a response.
+        ///
+        /// Handlers take `&self` and return `Send` futures, so a single builder may be
+        /// shared between tasks on a multi-threaded runtime such as tokio. As with
+        /// the synchronous builder, the incoming payload is validated before the
+        /// handler is invoked, the outgoing payload is validated before it is
+        /// returned, and any undefined method returns
+        /// [OCPPCallErrorCode::NotImplemented].
+        ///
+        /// Handlers may be implemented as `async fn`.
+        ///
+        /// # Example
+        /// ```
+        /// # async fn ocpp_async_example() {
+        /// use ocpp::*;
+        ///
+        /// struct MyResultBuilder {}
+        /// impl AsyncOCPPCallResultBuilder for MyResultBuilder {
+        ///     async fn heartbeat(&self, _req: HeartbeatRequest) -> Result<HeartbeatResponse, OCPPCallErrorCode> {
+        ///         Ok(HeartbeatResponse { current_time: chrono::Utc::now() })
+        ///     }
+        /// }
+        ///
+        /// let call = parse_call(r#"[2, "124", "Heartbeat", {}]"#).unwrap();
+        /// let res = MyResultBuilder {}.build_response(call).await.unwrap();
+        /// println!("Response: {:#?}", serde_json::to_string(&res));
+        /// # }
+        /// ```
+        pub trait AsyncOCPPCallResultBuilder: Sync {
+            $(
+                #[doc = concat!(
+                    "Handle ", stringify!($req),
+                    ". Returns [OCPPCallErrorCode::NotImplemented] by default."
+                )]
+                fn $method(
+                    &self,
+                    _req: $req,
+                ) -> impl Future<Output = Result<$res, OCPPCallErrorCode>> + Send
+                {
+                    async { Err(OCPPCallErrorCode::NotImplemented) }
+                }
+            )*
+
+            /// Build [OCPPCallResult] from [OCPPCall]
+            fn build_response(
+                &self,
+                call: OCPPCall,
+            ) -> impl Future<Output = Result<OCPPCallResult, OCPPCallError>>
+                   + Send {
+                async move {
+                    let OCPPCall {
+                        unique_id, payload, ..
+                    } = call;
+
+                    // Validate incoming payload
+                    if let Err(e) = payload.validate() {
+                        tracing::warn!("OCPP Request Invalid: {:?}", e);
+                        return Err(OCPPCallError::from_validate_error(
+                            &unique_id, &e,
+                        ));
+                    }
+
+                    let payload = match payload {
+                        $(
+                            OCPPCallPayload::$action(req) => self
+                                .$method(req)
+                                .await
+                                .map(OCPPCallResultPayload::$action)
+                                .map_err(|e| {
+                                    OCPPCallError::from_call(&unique_id, e)
+                                })?,
+                        )*
+                    };
+
+                    // Validate outgoing payload
+                    if let Err(e) = payload.validate() {
+                        tracing::error!("OCPP Response Invalid: {:?}", e);
+                        return Err(OCPPCallError::from_call(
+                            &unique_id,
+                            OCPPCallErrorCode::InternalError,
+                        ));
+                    }
+
+                    Ok(OCPPCallResult { unique_id, payload })
+                }
+            }
+        }
+    };
+}
+
+ocpp_actions!(async_call_result_builder);
@@ -0,0 +1,241 @@
+//! Local Authorization List with SendLocalList.req semantics
+//!
+//! [LocalAuthList] is the Local Authorization List of a Charge Point. It applies Full and Differential SendLocalList.req updates
+//! as a whole or not at all, answering with the [UpdateStatus] for the SendLocalList.conf, and reports its version for the
+//! GetLocalListVersion.conf.
+//!
+//! On the Central System side, a [LocalAuthList] can be kept as the snapshot last sent to each Charge Point, so that
+//! [LocalAuthList::differential_update] sends only the entries which changed.
+//!
+//! # Example
+//! ```
+//! use ocpp::*;
+//!
+//! let mut list = LocalAuthList::default();
+//! let req = SendLocalListRequest {
+//!     list_version: 1,
+//!     local_authorization_list: Some(vec![LocalAuthorizationList {
+//!         id_tag: String::from("TAG"),
+//!         id_tag_info: Some(IdTagInfo {
+//!             expiry_date: None,
+//!             parent_id_tag: None,
+//!             status: AuthorizationStatus::Accepted,
+//!         }),
+//!     }]),
+//!     update_type: UpdateType::Full,
+//! };
+//! assert_eq!(list.send_local_list(&req).status, UpdateStatus::Accepted);
+//! assert_eq!(list.get_local_list_version().list_version, 1);
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{
+    GetLocalListVersionResponse, IdTagInfo, LocalAuthorizationList,
+    SendLocalListRequest, SendLocalListResponse, UpdateStatus, UpdateType,
+};
+
+/// Name of the configuration key holding the maximum number of entries in the Local Authorization List
+pub const LOCAL_AUTH_LIST_MAX_LENGTH: &str = "LocalAuthListMaxLength";
+
+/// Name of the configuration key holding the maximum number of entries in a single SendLocalList.req
+pub const SEND_LOCAL_LIST_MAX_LENGTH: &str = "SendLocalListMaxLength";
+
+/// Local Authorization List of a Charge Point. See the [module documentation](self).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LocalAuthList {
+    version: u32,
+    entries: BTreeMap<String, IdTagInfo>,
+    local_auth_list_max_length: Option<usize>,
+    send_local_list_max_length: Option<usize>,
+}
+
+impl LocalAuthList {
+    /// Limit the number of entries in the list to the value of LocalAuthListMaxLength. Unlimited by default.
+    pub fn local_auth_list_max_length(mut self, max_length: usize) -> Self {
+        self.local_auth_list_max_length = Some(max_length);
+        self
+    }
+
+    /// Limit the number of entries in a single update to the value of SendLocalListMaxLength. Unlimited by default.
+    pub fn send_local_list_max_length(mut self, max_length: usize) -> Self {
+        self.send_local_list_max_length = Some(max_length);
+        self
+    }
+
+    /// Set the version of the list, e.g. of a Central System snapshot
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Version of the list, as set by the last applied update
+    pub fn version(&self) -> u32 { self.version }
+
+    /// Authorization of an id tag
+    pub fn get(&self, id_tag: &str) -> Option<&IdTagInfo> {
+        self.entries.get(id_tag)
+    }
+
+    /// Entries of the list, ordered by id tag
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &IdTagInfo)> {
+        self.entries.iter().map(|(id_tag, info)| (id_tag.as_str(), info))
+    }
+
+    /// Number of entries in the list
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    /// Whether the list has no entries
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+    /// Apply a SendLocalList.req, changing the list only if the update is accepted
+    pub fn send_local_list(
+        &mut self,
+        req: &SendLocalListRequest,
+    ) -> SendLocalListResponse {
+        SendLocalListResponse {
+            status: self.apply(req),
+        }
+    }
+
+    /// Answer a GetLocalListVersion.req. An empty list is reported as version 0.
+    pub fn get_local_list_version(&self) -> GetLocalListVersionResponse {
+        GetLocalListVersionResponse {
+            list_version: if self.is_empty() { 0 } else { self.version },
+        }
+    }
+
+    /// SendLocalList.req replacing the whole list of a Charge Point with this one
+    pub fn full_update(&self) -> SendLocalListRequest {
+        SendLocalListRequest {
+            list_version: self.version,
+            local_authorization_list: Some(
+                self.entries
+                    .iter()
+                    .map(|(id_tag, info)| LocalAuthorizationList {
+                        id_tag: id_tag.clone(),
+                        id_tag_info: Some(info.clone()),
+                    })
+                    .collect(),
+            ),
+            update_type: UpdateType::Full,
+        }
+    }
+
+    /// Smallest Differential SendLocalList.req turning this list into `target`: entries which were added or changed, and
+    /// entries without [IdTagInfo] for those which were removed. The update carries the version of `target`.
+    pub fn differential_update(
+        &self,
+        target: &LocalAuthList,
+    ) -> SendLocalListRequest {
+        let changed = target
+            .entries
+            .iter()
+            .filter(|(id_tag, info)| self.entries.get(*id_tag) != Some(*info));
+        let removed = self
+            .entries
+            .keys()
+            .filter(|id_tag| !target.entries.contains_key(*id_tag));
+
+        let mut updates: Vec<LocalAuthorizationList> = changed
+            .map(|(id_tag, info)| LocalAuthorizationList {
+                id_tag: id_tag.clone(),
+                id_tag_info: Some(info.clone()),
+            })
+            .chain(removed.map(|id_tag| LocalAuthorizationList {
+                id_tag: id_tag.clone(),
+                id_tag_info: None,
+            }))
+            .collect();
+        updates.sort_by(|a, b| a.id_tag.cmp(&b.id_tag));
+
+        SendLocalListRequest {
+            list_version: target.version,
+            local_authorization_list: Some(updates),
+            update_type: UpdateType::Differential,
+        }
+    }
+
+    fn apply(&mut self, req: &SendLocalListRequest) -> UpdateStatus {
+        let updates =
+            req.local_authorization_list.as_deref().unwrap_or_default();
+        if self
+            .send_local_list_max_length
+            .is_some_and(|max| updates.len() > max)
+        {
+            tracing::warn!(
+                "OCPP Local list update with {} entries exceeds {}",
+                updates.len(),
+                SEND_LOCAL_LIST_MAX_LENGTH
+            );
+            return UpdateStatus::Failed;
+        }
+        let mut id_tags = HashSet::new();
+        if !updates.iter().all(|update| id_tags.insert(&update.id_tag)) {
+            tracing::warn!("OCPP Local list update with duplicate id tags");
+            return UpdateStatus::Failed;
+        }
+
+        let entries = match req.update_type {
+            UpdateType::Full => {
+                let entries = updates
+                    .iter()
+                    .map(|update| {
+                        let info = update.id_tag_info.clone()?;
+                        Some((update.id_tag.clone(), info))
+                    })
+                    .collect::<Option<BTreeMap<_, _>>>();
+                let Some(entries) = entries else {
+                    tracing::warn!(
+                        "OCPP Full local list update without idTagInfo"
+                    );
+                    return UpdateStatus::Failed;
+                };
+                entries
+            },
+            UpdateType::Differential => {
+                if req.list_version <= self.version {
+                    return UpdateStatus::VersionMismatch;
+                }
+                let mut entries = self.entries.clone();
+                for update in updates {
+                    match &update.id_tag_info {
+                        Some(info) => {
+                            entries.insert(update.id_tag.clone(), info.clone());
+                        },
+                        None => {
+                            entries.remove(&update.id_tag);
+                        },
+                    }
+                }
+                entries
+            },
+        };
+
+        if self
+            .local_auth_list_max_length
+            .is_some_and(|max| entries.len() > max)
+        {
+            tracing::warn!(
+                "OCPP Local list with {} entries exceeds {}",
+                entries.len(),
+                LOCAL_AUTH_LIST_MAX_LENGTH
+            );
+            return UpdateStatus::Failed;
+        }
+
+        self.entries = entries;
+        self.version = req.list_version;
+        UpdateStatus::Accepted
+    }
+}
+
+impl FromIterator<(String, IdTagInfo)> for LocalAuthList {
+    /// Build a list, e.g. a Central System snapshot, with version 0. Set its version with [LocalAuthList::with_version].
+    fn from_iter<T: IntoIterator<Item = (String, IdTagInfo)>>(iter: T) -> Self {
+        LocalAuthList {
+            entries: iter.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+}
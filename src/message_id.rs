@@ -0,0 +1,152 @@
+//! Generation and validation of call unique ids
+//!
+//! OCPP-J requires the unique id of a call to be at most 36 characters long and never to be reused for another call by the same
+//! sender. [MessageIdGenerator] implementations produce compliant ids for outgoing calls, and [UniqueIdValidator] checks the ids of
+//! incoming calls.
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use thiserror::Error;
+
+/// Maximum length of a call unique id
+pub const MAX_UNIQUE_ID_LENGTH: usize = 36;
+
+/// Maximum length of a [PrefixedCounterGenerator] prefix, leaving room for the separator and the 20 digits of the largest counter
+pub const MAX_UNIQUE_ID_PREFIX_LENGTH: usize = MAX_UNIQUE_ID_LENGTH - 21;
+
+/// Source of unique ids for outgoing calls
+pub trait MessageIdGenerator: Send + Sync {
+    /// Get the next unique id
+    fn next_id(&self) -> String;
+}
+
+/// Generates random UUIDv4 unique ids, e.g. `"8d5ba5a4-7e4c-4b6a-9b2e-1c0f6e0fb4b1"`
+#[derive(Debug, Default, Clone)]
+pub struct UuidGenerator;
+
+impl MessageIdGenerator for UuidGenerator {
+    fn next_id(&self) -> String { uuid::Uuid::new_v4().to_string() }
+}
+
+/// Generates unique ids from a monotonic counter, e.g. `"1"`, `"2"`, ...
+#[derive(Debug, Default)]
+pub struct CounterGenerator {
+    next: AtomicU64,
+}
+
+impl CounterGenerator {
+    /// Create a generator whose first id is `start`
+    pub fn new(start: u64) -> Self {
+        CounterGenerator {
+            next: AtomicU64::new(start),
+        }
+    }
+}
+
+impl MessageIdGenerator for CounterGenerator {
+    fn next_id(&self) -> String {
+        self.next.fetch_add(1, Ordering::Relaxed).to_string()
+    }
+}
+
+/// Generates unique ids from a fixed prefix and a monotonic counter, e.g. `"63:1"`, `"63:2"`, ...
+///
+/// A prefix which differs between sessions, such as a connection count, avoids reusing ids after a restart.
+#[derive(Debug)]
+pub struct PrefixedCounterGenerator {
+    prefix: String,
+    next: AtomicU64,
+}
+
+impl PrefixedCounterGenerator {
+    /// Create a generator producing ids of the form `"{prefix}:{counter}"`, starting from counter 1. Fails if the prefix is
+    /// longer than [MAX_UNIQUE_ID_PREFIX_LENGTH], as the ids could then exceed [MAX_UNIQUE_ID_LENGTH].
+    pub fn new(prefix: impl Into<String>) -> Result<Self, UniqueIdError> {
+        let prefix = prefix.into();
+        if prefix.chars().count() > MAX_UNIQUE_ID_PREFIX_LENGTH {
+            return Err(UniqueIdError::PrefixTooLong(prefix));
+        }
+        Ok(PrefixedCounterGenerator {
+            prefix,
+            next: AtomicU64::new(1),
+        })
+    }
+}
+
+impl MessageIdGenerator for PrefixedCounterGenerator {
+    fn next_id(&self) -> String {
+        format!(
+            "{}:{}",
+            self.prefix,
+            self.next.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+}
+
+/// Errors returned when validating a unique id
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum UniqueIdError {
+    /// The unique id is empty
+    #[error("unique id is empty")]
+    Empty,
+    /// The unique id is longer than [MAX_UNIQUE_ID_LENGTH]
+    #[error("unique id {0} is longer than 36 characters")]
+    TooLong(String),
+    /// The prefix of a [PrefixedCounterGenerator] is longer than [MAX_UNIQUE_ID_PREFIX_LENGTH]
+    #[error("unique id prefix {0} is longer than 15 characters")]
+    PrefixTooLong(String),
+    /// The unique id was already used by a recent call
+    #[error("unique id {0} was already used")]
+    Duplicate(String),
+}
+
+/// Check that a unique id is not empty and at most [MAX_UNIQUE_ID_LENGTH] characters long
+pub fn validate_unique_id(unique_id: &str) -> Result<(), UniqueIdError> {
+    if unique_id.is_empty() {
+        return Err(UniqueIdError::Empty);
+    }
+    if unique_id.chars().count() > MAX_UNIQUE_ID_LENGTH {
+        return Err(UniqueIdError::TooLong(String::from(unique_id)));
+    }
+    Ok(())
+}
+
+/// Validates incoming unique ids, rejecting ids reused within the last `window` calls
+#[derive(Debug, Clone)]
+pub struct UniqueIdValidator {
+    window: usize,
+    recent: VecDeque<String>,
+    seen: HashSet<String>,
+}
+
+impl UniqueIdValidator {
+    /// Create a validator remembering the last `window` unique ids
+    pub fn new(window: usize) -> Self {
+        UniqueIdValidator {
+            window,
+            recent: VecDeque::with_capacity(window),
+            seen: HashSet::with_capacity(window),
+        }
+    }
+
+    /// Validate a unique id and remember it if it is valid
+    pub fn check(&mut self, unique_id: &str) -> Result<(), UniqueIdError> {
+        validate_unique_id(unique_id)?;
+        if self.seen.contains(unique_id) {
+            return Err(UniqueIdError::Duplicate(String::from(unique_id)));
+        }
+        if self.window == 0 {
+            return Ok(());
+        }
+
+        if self.recent.len() == self.window {
+            if let Some(oldest) = self.recent.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        self.recent.push_back(String::from(unique_id));
+        self.seen.insert(String::from(unique_id));
+        Ok(())
+    }
+}
@@ -0,0 +1,377 @@
+//! Queueing of transaction-related messages while offline
+//!
+//! A Charge Point SHOULD queue StartTransaction.req, StopTransaction.req and MeterValues.req belonging to a transaction while it
+//! is offline, and deliver them in order once the connection is restored. Other messages are not queued: they are dropped by
+//! [MessageQueue::enqueue].
+//!
+//! When the Central System fails to process a transaction-related message, it is retried according to [TransactionRetry], which
+//! corresponds to the TransactionMessageAttempts and TransactionMessageRetryInterval configuration keys. Failing to send a
+//! message because the connection is down is not a failed attempt.
+//!
+//! [MemoryMessageQueue] keeps messages in memory, while [FileMessageQueue] persists them in an append-only log so that they
+//! survive a restart.
+//!
+//! # Example
+//! ```
+//! # fn ocpp_message_queue_example() -> std::io::Result<()> {
+//! use ocpp::*;
+//!
+//! let mut queue = MemoryMessageQueue::default();
+//! let retry = TransactionRetry::default();
+//!
+//! // Only transaction-related calls are queued while offline
+//! let call: OCPPCall = (String::from("1"), HeartbeatRequest {}.into()).into();
+//! assert!(!queue.enqueue(call)?);
+//!
+//! // Once online, send each due message in order
+//! while let Some(call) = queue.next_due(chrono::Utc::now()) {
+//!     let delivered = true; // send `call` and await its response
+//!     if delivered {
+//!         queue.delivered()?;
+//!     } else {
+//!         queue.failed(&retry, chrono::Utc::now())?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{OCPPCall, OCPPCallPayload};
+
+/// Name of the configuration key holding the number of attempts for a transaction-related message
+pub const TRANSACTION_MESSAGE_ATTEMPTS: &str = "TransactionMessageAttempts";
+
+/// Name of the configuration key holding the interval in seconds between attempts for a transaction-related message
+pub const TRANSACTION_MESSAGE_RETRY_INTERVAL: &str =
+    "TransactionMessageRetryInterval";
+
+impl OCPPCallPayload {
+    /// Whether the payload is a transaction-related message, which is queued while offline
+    pub fn is_transaction_related(&self) -> bool {
+        match self {
+            OCPPCallPayload::StartTransaction(_)
+            | OCPPCallPayload::StopTransaction(_) => true,
+            OCPPCallPayload::MeterValues(req) => req.transaction_id.is_some(),
+            _ => false,
+        }
+    }
+}
+
+/// Retry of transaction-related messages which the Central System failed to process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionRetry {
+    /// TransactionMessageAttempts, the number of times a message is sent before it is discarded
+    pub attempts: u32,
+    /// TransactionMessageRetryInterval. The n-th retry waits for n times this interval.
+    pub retry_interval: Duration,
+}
+
+impl TransactionRetry {
+    /// Read the retry semantics from the values of the TransactionMessageAttempts and TransactionMessageRetryInterval
+    /// configuration keys, or `None` if either is not a non-negative integer
+    pub fn from_configuration(
+        attempts: &str,
+        retry_interval: &str,
+    ) -> Option<Self> {
+        Some(TransactionRetry {
+            attempts: attempts.trim().parse().ok()?,
+            retry_interval: Duration::from_secs(
+                retry_interval.trim().parse().ok()?,
+            ),
+        })
+    }
+
+    /// Time to wait before sending a message again after `attempts` failed attempts
+    pub fn delay(&self, attempts: u32) -> Duration {
+        self.retry_interval.saturating_mul(attempts)
+    }
+}
+
+impl Default for TransactionRetry {
+    /// 3 attempts, 60 seconds apart
+    fn default() -> Self {
+        TransactionRetry {
+            attempts: 3,
+            retry_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+/// A transaction-related call awaiting delivery
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedMessage {
+    /// The call, serialized as an OCPP-J frame
+    pub call: OCPPCall,
+    /// Number of failed attempts to deliver the call
+    pub attempts: u32,
+    /// Time before which the call must not be sent again, if it has failed before
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+/// First in, first out queue of transaction-related messages. See the [module documentation](self).
+///
+/// Implementations provide the storage, while the provided methods apply the queueing and retry semantics.
+pub trait MessageQueue {
+    /// Add a message to the back of the queue
+    fn push_back(&mut self, message: QueuedMessage) -> io::Result<()>;
+
+    /// The oldest message in the queue
+    fn front(&self) -> Option<&QueuedMessage>;
+
+    /// Remove the oldest message from the queue
+    fn pop_front(&mut self) -> io::Result<Option<QueuedMessage>>;
+
+    /// Record a failed attempt to deliver the oldest message
+    fn retry_front(
+        &mut self,
+        attempts: u32,
+        retry_at: DateTime<Utc>,
+    ) -> io::Result<()>;
+
+    /// Number of queued messages
+    fn len(&self) -> usize;
+
+    /// Whether the queue is empty
+    fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Queue a call if it is transaction-related, returning whether it was queued. Other calls are dropped.
+    fn enqueue(&mut self, call: OCPPCall) -> io::Result<bool> {
+        if !call.payload.is_transaction_related() {
+            tracing::debug!(
+                "OCPP Dropped {} while offline",
+                call.payload.action()
+            );
+            return Ok(false);
+        }
+        self.push_back(QueuedMessage {
+            call,
+            attempts: 0,
+            retry_at: None,
+        })?;
+        Ok(true)
+    }
+
+    /// The oldest call, if it may be sent at `now`. Later calls are held back until it has been delivered or discarded.
+    fn next_due(&self, now: DateTime<Utc>) -> Option<&OCPPCall> {
+        self.front()
+            .filter(|message| message.retry_at.map_or(true, |at| at <= now))
+            .map(|message| &message.call)
+    }
+
+    /// Remove the oldest call after the Central System has processed it
+    fn delivered(&mut self) -> io::Result<Option<QueuedMessage>> {
+        self.pop_front()
+    }
+
+    /// Record that the Central System failed to process the oldest call at `now`. Returns the message if it has used all its
+    /// attempts and was discarded.
+    fn failed(
+        &mut self,
+        retry: &TransactionRetry,
+        now: DateTime<Utc>,
+    ) -> io::Result<Option<QueuedMessage>> {
+        let Some(message) = self.front() else {
+            return Ok(None);
+        };
+        let attempts = message.attempts.saturating_add(1);
+        if attempts >= retry.attempts {
+            tracing::warn!(
+                "OCPP Discarded {} after {} attempts",
+                message.call.payload.action(),
+                attempts
+            );
+            return self.pop_front();
+        }
+
+        let retry_at = chrono::Duration::from_std(retry.delay(attempts))
+            .ok()
+            .and_then(|delay| now.checked_add_signed(delay))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        self.retry_front(attempts, retry_at)?;
+        Ok(None)
+    }
+}
+
+/// [MessageQueue] held in memory, lost on restart
+#[derive(Debug, Clone, Default)]
+pub struct MemoryMessageQueue {
+    messages: VecDeque<QueuedMessage>,
+}
+
+impl MessageQueue for MemoryMessageQueue {
+    fn push_back(&mut self, message: QueuedMessage) -> io::Result<()> {
+        self.messages.push_back(message);
+        Ok(())
+    }
+
+    fn front(&self) -> Option<&QueuedMessage> { self.messages.front() }
+
+    fn pop_front(&mut self) -> io::Result<Option<QueuedMessage>> {
+        Ok(self.messages.pop_front())
+    }
+
+    fn retry_front(
+        &mut self,
+        attempts: u32,
+        retry_at: DateTime<Utc>,
+    ) -> io::Result<()> {
+        if let Some(message) = self.messages.front_mut() {
+            message.attempts = attempts;
+            message.retry_at = Some(retry_at);
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize { self.messages.len() }
+}
+
+/// Change to a [FileMessageQueue], written as one line of JSON to its log
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Record {
+    Push(Box<QueuedMessage>),
+    Pop,
+    Retry {
+        attempts: u32,
+        retry_at: DateTime<Utc>,
+    },
+}
+
+/// [MessageQueue] persisted in an append-only log file
+///
+/// Every change is appended and synced to the file before it is applied. The log is compacted when the queue is opened and
+/// truncated whenever the queue becomes empty.
+#[derive(Debug)]
+pub struct FileMessageQueue {
+    path: PathBuf,
+    file: File,
+    messages: MemoryMessageQueue,
+}
+
+impl FileMessageQueue {
+    /// Open the queue stored at `path`, creating it if it does not exist
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut messages = MemoryMessageQueue::default();
+
+        match File::open(&path) {
+            Ok(file) => {
+                let mut lines = BufReader::new(file).lines().peekable();
+                while let Some(line) = lines.next() {
+                    let line = line?;
+                    let record = match serde_json::from_str(&line) {
+                        Ok(record) => record,
+                        // A write interrupted by a crash leaves a partial last line
+                        Err(e) if lines.peek().is_none() => {
+                            tracing::warn!(
+                                "OCPP Ignored incomplete queue record: {}",
+                                e
+                            );
+                            break;
+                        },
+                        Err(e) => {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                e,
+                            ))
+                        },
+                    };
+                    match record {
+                        Record::Push(message) => {
+                            messages.push_back(*message)?
+                        },
+                        Record::Pop => {
+                            messages.pop_front()?;
+                        },
+                        Record::Retry { attempts, retry_at } => {
+                            messages.retry_front(attempts, retry_at)?
+                        },
+                    }
+                }
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+            Err(e) => return Err(e),
+        }
+
+        let file = compact(&path, &messages)?;
+        Ok(FileMessageQueue {
+            path,
+            file,
+            messages,
+        })
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &Path { &self.path }
+
+    fn append(&mut self, record: &Record) -> io::Result<()> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        self.file.write_all(line.as_bytes())?;
+        self.file.sync_data()
+    }
+}
+
+/// Rewrite the log with only the current messages, returning it opened for appending
+fn compact(path: &Path, messages: &MemoryMessageQueue) -> io::Result<File> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".compact");
+    let temporary = PathBuf::from(temporary);
+    let mut file = File::create(&temporary)?;
+    for message in &messages.messages {
+        let mut line =
+            serde_json::to_string(&Record::Push(Box::new(message.clone())))?;
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
+    }
+    file.sync_all()?;
+    fs::rename(&temporary, path)?;
+
+    OpenOptions::new().append(true).open(path)
+}
+
+impl MessageQueue for FileMessageQueue {
+    fn push_back(&mut self, message: QueuedMessage) -> io::Result<()> {
+        self.append(&Record::Push(Box::new(message.clone())))?;
+        self.messages.push_back(message)
+    }
+
+    fn front(&self) -> Option<&QueuedMessage> { self.messages.front() }
+
+    fn pop_front(&mut self) -> io::Result<Option<QueuedMessage>> {
+        if self.messages.is_empty() {
+            return Ok(None);
+        }
+        if self.messages.len() == 1 {
+            self.file.set_len(0)?;
+            self.file.sync_data()?;
+        } else {
+            self.append(&Record::Pop)?;
+        }
+        self.messages.pop_front()
+    }
+
+    fn retry_front(
+        &mut self,
+        attempts: u32,
+        retry_at: DateTime<Utc>,
+    ) -> io::Result<()> {
+        if self.messages.is_empty() {
+            return Ok(());
+        }
+        self.append(&Record::Retry { attempts, retry_at })?;
+        self.messages.retry_front(attempts, retry_at)
+    }
+
+    fn len(&self) -> usize { self.messages.len() }
+}
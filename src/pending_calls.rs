@@ -0,0 +1,197 @@
+//! Tracking of outgoing calls awaiting a response
+//!
+//! A call result does not carry its action, so it can only be decoded by looking up the call it answers. [PendingCalls] records
+//! each outgoing [OCPPCall] and resolves the matching [OCPPCallResultUnknown] or [OCPPCallError] back to it.
+//!
+//! OCPP-J only allows a single call to be outstanding in each direction, so a new call is refused until the previous one has been
+//! answered or has timed out.
+
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+use crate::{
+    OCPPCall, OCPPCallAction, OCPPCallError, OCPPCallResult,
+    OCPPCallResultUnknown,
+};
+
+/// Errors returned by [PendingCalls]
+#[derive(Debug, Error)]
+pub enum PendingCallsError {
+    /// Another call is still awaiting a response
+    #[error("call {0} is still outstanding")]
+    CallOutstanding(String),
+    /// A response was received for a call which is not outstanding, either because it was never sent or because it timed out
+    #[error("no outstanding call with unique id {0}")]
+    UnknownUniqueId(String),
+    /// The response could not be decoded as a response to the outstanding call
+    #[error("invalid response to {action} call {unique_id}: {source}")]
+    InvalidResult {
+        /// Unique id of the call
+        unique_id: String,
+        /// Action of the call
+        action: OCPPCallAction,
+        /// Decoding error
+        source: serde_json::Error,
+    },
+}
+
+/// An outgoing call awaiting a response
+#[derive(Debug, Clone)]
+pub struct PendingCall {
+    /// Unique id of the call
+    pub unique_id: String,
+    /// Action of the call
+    pub action: OCPPCallAction,
+    /// Time at which the call was recorded
+    pub sent_at: Instant,
+}
+
+/// Registry of outgoing calls awaiting a response. See the [module documentation](self).
+///
+/// # Example
+/// ```
+/// # fn ocpp_pending_calls_example() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::time::Duration;
+///
+/// use ocpp::*;
+///
+/// let mut pending = PendingCalls::new(Duration::from_secs(30));
+///
+/// let call: OCPPCall = (String::from("63:1"), HeartbeatRequest {}.into()).into();
+/// pending.push(&call)?;
+///
+/// let json = r#"[3,"63:1",{"currentTime":"2022-01-24T04:30:50.621Z"}]"#;
+/// if let OCPPMessage::CallResultUnknown(unknown) = serde_json::from_str(json)? {
+///     let result = pending.resolve_result(unknown)?;
+///     println!("Call Result: {:#?}", result);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PendingCalls {
+    timeout: Duration,
+    outstanding: Option<PendingCall>,
+}
+
+impl PendingCalls {
+    /// Create a registry whose calls expire after `timeout` without a response
+    pub fn new(timeout: Duration) -> Self {
+        PendingCalls {
+            timeout,
+            outstanding: None,
+        }
+    }
+
+    /// Time after which an unanswered call expires
+    pub fn timeout(&self) -> Duration { self.timeout }
+
+    /// The call currently awaiting a response, if any
+    pub fn outstanding(&self) -> Option<&PendingCall> {
+        self.outstanding.as_ref()
+    }
+
+    /// Record an outgoing call. Fails if another call is still outstanding and has not expired.
+    pub fn push(&mut self, call: &OCPPCall) -> Result<(), PendingCallsError> {
+        self.push_at(call, Instant::now())
+    }
+
+    /// Record an outgoing call as sent at the given time
+    pub fn push_at(
+        &mut self,
+        call: &OCPPCall,
+        now: Instant,
+    ) -> Result<(), PendingCallsError> {
+        self.expire_at(now);
+        if let Some(pending) = &self.outstanding {
+            return Err(PendingCallsError::CallOutstanding(
+                pending.unique_id.clone(),
+            ));
+        }
+
+        self.outstanding = Some(PendingCall {
+            unique_id: call.unique_id.clone(),
+            action: call.payload.action(),
+            sent_at: now,
+        });
+        Ok(())
+    }
+
+    /// Remove the outstanding call if it has expired, returning it
+    pub fn expire(&mut self) -> Option<PendingCall> {
+        self.expire_at(Instant::now())
+    }
+
+    /// Remove the outstanding call if it has expired at the given time, returning it
+    pub fn expire_at(&mut self, now: Instant) -> Option<PendingCall> {
+        match &self.outstanding {
+            Some(pending)
+                if now.saturating_duration_since(pending.sent_at)
+                    >= self.timeout =>
+            {
+                tracing::warn!("OCPP Call {} timed out", pending.unique_id);
+                self.outstanding.take()
+            },
+            _ => None,
+        }
+    }
+
+    /// Decode a call result as the response to the outstanding call with the same unique id
+    pub fn resolve_result(
+        &mut self,
+        unknown: OCPPCallResultUnknown,
+    ) -> Result<OCPPCallResult, PendingCallsError> {
+        self.resolve_result_at(unknown, Instant::now())
+    }
+
+    /// Decode a call result received at the given time. A call which has expired by then is no longer outstanding.
+    pub fn resolve_result_at(
+        &mut self,
+        unknown: OCPPCallResultUnknown,
+        now: Instant,
+    ) -> Result<OCPPCallResult, PendingCallsError> {
+        let pending = self.take_at(&unknown.unique_id, now)?;
+
+        OCPPCallResult::from_unknown(&pending.action, unknown).map_err(
+            |source| PendingCallsError::InvalidResult {
+                unique_id: pending.unique_id,
+                action: pending.action,
+                source,
+            },
+        )
+    }
+
+    /// Match a call error to the outstanding call with the same unique id, returning the action of the failed call
+    pub fn resolve_error(
+        &mut self,
+        error: &OCPPCallError,
+    ) -> Result<OCPPCallAction, PendingCallsError> {
+        self.resolve_error_at(error, Instant::now())
+    }
+
+    /// Match a call error received at the given time. A call which has expired by then is no longer outstanding.
+    pub fn resolve_error_at(
+        &mut self,
+        error: &OCPPCallError,
+        now: Instant,
+    ) -> Result<OCPPCallAction, PendingCallsError> {
+        self.take_at(&error.unique_id, now).map(|pending| pending.action)
+    }
+
+    fn take_at(
+        &mut self,
+        unique_id: &str,
+        now: Instant,
+    ) -> Result<PendingCall, PendingCallsError> {
+        self.expire_at(now);
+        match self.outstanding.take() {
+            Some(pending) if pending.unique_id == unique_id => Ok(pending),
+            other => {
+                self.outstanding = other;
+                tracing::warn!("OCPP Response to unknown call {}", unique_id);
+                Err(PendingCallsError::UnknownUniqueId(String::from(unique_id)))
+            },
+        }
+    }
+}
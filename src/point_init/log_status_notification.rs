@@ -0,0 +1,53 @@
+//! Update from charge point to inform the Central System about the status of a log upload.
+//!
+//! # Behaviour
+//! Part of the OCPP 1.6 Security Whitepaper. The Charge Point SHALL send LogStatusNotification.req PDUs to inform the Central System
+//! about the progress of a log upload requested with a [GetLog.req](crate::server_init::get_log). The Charge Point SHALL only send the
+//! status Idle after receipt of an ExtendedTriggerMessage for a LogStatusNotification, when it is not busy uploading a log.
+//!
+//! # Response
+//! Upon receipt of a LogStatusNotification.req PDU, the Central System SHALL respond with a LogStatusNotification.conf.
+
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use strum_macros::Display;
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/LogStatusNotification.json")]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the LogStatusNotification.req PDU sent by the Charge Point to the Central System.
+pub struct LogStatusNotificationRequest {
+    /// Required. This contains the status of the log upload.
+    pub status: UploadLogStatus,
+    /// Optional. The request id that was provided in the GetLog.req that started this log upload.
+    pub request_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+/// Status of a log upload as reported in LogStatusNotification.req.
+pub enum UploadLogStatus {
+    /// A badly formatted packet or other protocol incompatibility was detected.
+    BadMessage,
+    /// The Charge Point is not uploading a log file. Idle SHALL only be used when the message was triggered by a ExtendedTriggerMessage.req.
+    Idle,
+    /// The server does not support the operation.
+    NotSupportedOperation,
+    /// Insufficient permissions to perform the operation.
+    PermissionDenied,
+    /// File has been uploaded successfully.
+    Uploaded,
+    /// Failed to upload the requested file.
+    UploadFailure,
+    /// File is being uploaded.
+    Uploading,
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate("../json_schemas/LogStatusNotificationResponse.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the LogStatusNotification.conf PDU sent by the Central System to the Charge Point in response to a LogStatusNotification.req PDU.
+pub struct LogStatusNotificationResponse {}
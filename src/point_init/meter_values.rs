@@ -20,9 +20,9 @@
 //! The request PDU SHALL contain for each sample:
 //!
 //! 1. The id of the Connector from which samples were taken. If the connectorId is 0, it is associated with the entire Charge Point. If the connectorId
-//!  is 0 and the Measurand is energy related, the sample SHOULD be taken from the main energy meter.
+//!    is 0 and the Measurand is energy related, the sample SHOULD be taken from the main energy meter.
 //! 2. The transactionId of the transaction to which these values are related, if applicable. If there is no transaction in progress or if the values are
-//!  taken from the main meter, then transaction id may be omitted.
+//!    taken from the main meter, then transaction id may be omitted.
 //! 3. One or more meterValue elements, of type MeterValue, each representing a set of one or more data values taken at a particular point in time.
 //!
 //! Each MeterValue element contains a timestamp and a set of one or more individual sampledvalue elements, all captured at the same point in time.
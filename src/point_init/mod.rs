@@ -4,7 +4,11 @@ pub mod boot_notification;
 pub mod diagnostic_status_notification;
 pub mod firmware_status_notification;
 pub mod heartbeat;
+pub mod log_status_notification;
 pub mod meter_values;
+pub mod security_event_notification;
+pub mod sign_certificate;
+pub mod signed_firmware_status_notification;
 pub mod start_transaction;
 pub mod status_notification;
 pub mod stop_transaction;
@@ -14,7 +18,11 @@ pub use boot_notification::*;
 pub use diagnostic_status_notification::*;
 pub use firmware_status_notification::*;
 pub use heartbeat::*;
+pub use log_status_notification::*;
 pub use meter_values::*;
+pub use security_event_notification::*;
+pub use sign_certificate::*;
+pub use signed_firmware_status_notification::*;
 pub use start_transaction::*;
 pub use status_notification::*;
 pub use stop_transaction::*;
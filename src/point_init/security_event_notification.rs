@@ -0,0 +1,201 @@
+//! Notification from chargepoint to inform the Central System of a security event
+//!
+//! # Behaviour
+//! Part of the OCPP 1.6 Security Whitepaper. The Charge Point SHALL send a SecurityEventNotification.req to inform the Central System
+//! of critical security events. The Charge Point SHALL store all security events in a security log, which can be retrieved with a
+//! GetLog.req. Events which are not critical are only stored in the security log and are not required to be sent to the Central System.
+//!
+//! The whitepaper defines a list of standard security events, each of which is either critical or not. These are modelled by
+//! [SecurityEvent], vendor specific events are passed through as [SecurityEvent::Other].
+//!
+//! # Response
+//! Upon receipt of a SecurityEventNotification.req PDU, the Central System SHALL respond with a SecurityEventNotification.conf PDU.
+
+use chrono::{DateTime, Utc};
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/SecurityEventNotification.json")]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the SecurityEventNotification.req PDU sent by the Charge Point to the Central System.
+pub struct SecurityEventNotificationRequest {
+    /// Required. Type of the security event.
+    pub r#type: SecurityEvent,
+    /// Required. Date and time at which the event occurred.
+    pub timestamp: DateTime<Utc>,
+    /// Optional. Additional information about the occurred security event.
+    pub tech_info: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+#[serde(from = "String", into = "String")]
+/// Security event type reported in SecurityEventNotification.req, as listed in the OCPP 1.6 Security Whitepaper.
+///
+/// Use [SecurityEvent::is_critical] to determine whether an event must be pushed to the Central System.
+pub enum SecurityEvent {
+    /// The Charge Point firmware is updated. Critical.
+    FirmwareUpdated,
+    /// The authentication credentials provided by the Charge Point were rejected by the Central System.
+    FailedToAuthenticateAtCentralSystem,
+    /// The authentication credentials provided by the Central System were rejected by the Charge Point.
+    CentralSystemFailedToAuthenticate,
+    /// The system time on the Charge Point was changed. Critical.
+    SettingSystemTime,
+    /// The Charge Point has booted. Critical.
+    StartupOfTheDevice,
+    /// The Charge Point was rebooted or reset. Critical.
+    ResetOrReboot,
+    /// The security log was cleared. Critical.
+    SecurityLogWasCleared,
+    /// Security parameters, such as keys or the security profile used, were changed. Critical.
+    ReconfigurationOfSecurityParameters,
+    /// The Flash or RAM memory of the Charge Point is getting full. Critical.
+    MemoryExhaustion,
+    /// The Charge Point has received messages that are not valid OCPP messages, if signed messages, signage invalid/incorrect.
+    InvalidMessages,
+    /// The Charge Point has received a replayed message (other than the Central System trying to resend a message because it there was for example a network problem).
+    AttemptedReplayAttacks,
+    /// The physical tamper detection sensor was triggered. Critical.
+    TamperDetectionActivated,
+    /// The firmware signature is not valid.
+    InvalidFirmwareSignature,
+    /// The certificate used to verify the firmware signature is not valid.
+    InvalidFirmwareSigningCertificate,
+    /// The certificate that the Central System uses was not valid or could not be verified.
+    InvalidCentralSystemCertificate,
+    /// The certificate sent to the Charge Point using the SignCertificate.conf message is not a valid certificate.
+    InvalidChargePointCertificate,
+    /// The TLS version used by the Central System is lower than 1.2 and is not allowed by the security specification.
+    InvalidTLSVersion,
+    /// The Central System did only allow connections using TLS cipher suites that are not allowed by the security specification.
+    InvalidTLSCipherSuite,
+    /// Vendor specific security event, passed through as received.
+    Other(String),
+}
+
+impl SecurityEvent {
+    /// Whether the whitepaper marks this event as critical, i.e. it SHALL be sent to the Central System with a
+    /// SecurityEventNotification.req. Vendor specific events are never considered critical.
+    pub fn is_critical(&self) -> bool {
+        matches!(
+            self,
+            SecurityEvent::FirmwareUpdated
+                | SecurityEvent::SettingSystemTime
+                | SecurityEvent::StartupOfTheDevice
+                | SecurityEvent::ResetOrReboot
+                | SecurityEvent::SecurityLogWasCleared
+                | SecurityEvent::ReconfigurationOfSecurityParameters
+                | SecurityEvent::MemoryExhaustion
+                | SecurityEvent::TamperDetectionActivated
+        )
+    }
+
+    /// The event type as sent on the wire.
+    pub fn as_str(&self) -> &str {
+        match self {
+            SecurityEvent::FirmwareUpdated => "FirmwareUpdated",
+            SecurityEvent::FailedToAuthenticateAtCentralSystem => {
+                "FailedToAuthenticateAtCentralSystem"
+            },
+            SecurityEvent::CentralSystemFailedToAuthenticate => {
+                "CentralSystemFailedToAuthenticate"
+            },
+            SecurityEvent::SettingSystemTime => "SettingSystemTime",
+            SecurityEvent::StartupOfTheDevice => "StartupOfTheDevice",
+            SecurityEvent::ResetOrReboot => "ResetOrReboot",
+            SecurityEvent::SecurityLogWasCleared => "SecurityLogWasCleared",
+            SecurityEvent::ReconfigurationOfSecurityParameters => {
+                "ReconfigurationOfSecurityParameters"
+            },
+            SecurityEvent::MemoryExhaustion => "MemoryExhaustion",
+            SecurityEvent::InvalidMessages => "InvalidMessages",
+            SecurityEvent::AttemptedReplayAttacks => "AttemptedReplayAttacks",
+            SecurityEvent::TamperDetectionActivated => {
+                "TamperDetectionActivated"
+            },
+            SecurityEvent::InvalidFirmwareSignature => {
+                "InvalidFirmwareSignature"
+            },
+            SecurityEvent::InvalidFirmwareSigningCertificate => {
+                "InvalidFirmwareSigningCertificate"
+            },
+            SecurityEvent::InvalidCentralSystemCertificate => {
+                "InvalidCentralSystemCertificate"
+            },
+            SecurityEvent::InvalidChargePointCertificate => {
+                "InvalidChargePointCertificate"
+            },
+            SecurityEvent::InvalidTLSVersion => "InvalidTLSVersion",
+            SecurityEvent::InvalidTLSCipherSuite => "InvalidTLSCipherSuite",
+            SecurityEvent::Other(other) => other,
+        }
+    }
+}
+
+impl From<String> for SecurityEvent {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "FirmwareUpdated" => SecurityEvent::FirmwareUpdated,
+            "FailedToAuthenticateAtCentralSystem" => {
+                SecurityEvent::FailedToAuthenticateAtCentralSystem
+            },
+            "CentralSystemFailedToAuthenticate" => {
+                SecurityEvent::CentralSystemFailedToAuthenticate
+            },
+            "SettingSystemTime" => SecurityEvent::SettingSystemTime,
+            "StartupOfTheDevice" => SecurityEvent::StartupOfTheDevice,
+            "ResetOrReboot" => SecurityEvent::ResetOrReboot,
+            "SecurityLogWasCleared" => SecurityEvent::SecurityLogWasCleared,
+            "ReconfigurationOfSecurityParameters" => {
+                SecurityEvent::ReconfigurationOfSecurityParameters
+            },
+            "MemoryExhaustion" => SecurityEvent::MemoryExhaustion,
+            "InvalidMessages" => SecurityEvent::InvalidMessages,
+            "AttemptedReplayAttacks" => SecurityEvent::AttemptedReplayAttacks,
+            "TamperDetectionActivated" => {
+                SecurityEvent::TamperDetectionActivated
+            },
+            "InvalidFirmwareSignature" => {
+                SecurityEvent::InvalidFirmwareSignature
+            },
+            "InvalidFirmwareSigningCertificate" => {
+                SecurityEvent::InvalidFirmwareSigningCertificate
+            },
+            "InvalidCentralSystemCertificate" => {
+                SecurityEvent::InvalidCentralSystemCertificate
+            },
+            "InvalidChargePointCertificate" => {
+                SecurityEvent::InvalidChargePointCertificate
+            },
+            "InvalidTLSVersion" => SecurityEvent::InvalidTLSVersion,
+            "InvalidTLSCipherSuite" => SecurityEvent::InvalidTLSCipherSuite,
+            _ => SecurityEvent::Other(value),
+        }
+    }
+}
+
+impl From<SecurityEvent> for String {
+    fn from(value: SecurityEvent) -> Self {
+        match value {
+            SecurityEvent::Other(other) => other,
+            event => String::from(event.as_str()),
+        }
+    }
+}
+
+impl std::fmt::Display for SecurityEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate("../json_schemas/SecurityEventNotificationResponse.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the SecurityEventNotification.conf PDU sent by the Central System to the Charge Point in response to a SecurityEventNotification.req PDU.
+pub struct SecurityEventNotificationResponse {}
@@ -0,0 +1,37 @@
+//! Request from chargepoint to have a new certificate signed by the Central System's Certificate Authority
+//!
+//! # Behaviour
+//! Part of the OCPP 1.6 Security Whitepaper. The Charge Point SHALL generate a new public / private key pair and send a SignCertificate.req
+//! containing a certificate signing request when its certificate is about to expire, or when triggered through an
+//! ExtendedTriggerMessage.req. The private key SHALL NOT leave the Charge Point.
+//!
+//! # Response
+//! Upon receipt of a SignCertificate.req PDU, the Central System SHALL respond with a SignCertificate.conf PDU. When the Central
+//! System accepts the request it forwards the CSR to the Certificate Authority, and sends the signed certificate to the Charge Point
+//! in a [CertificateSigned.req](crate::server_init::certificate_signed).
+
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+
+pub use crate::common_types::SimpleStatus;
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/SignCertificate.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the SignCertificate.req PDU sent by the Charge Point to the Central System.
+pub struct SignCertificateRequest {
+    /// Required. The Charge Point SHALL send the public key in form of a Certificate Signing Request (CSR) as described in RFC 2986
+    /// and then PEM encoded.
+    pub csr: String,
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate("../json_schemas/SignCertificateResponse.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the SignCertificate.conf PDU sent by the Central System to the Charge Point in response to a SignCertificate.req PDU.
+pub struct SignCertificateResponse {
+    /// Required. Specifies whether the Central System can process the request.
+    pub status: SimpleStatus,
+}
@@ -0,0 +1,74 @@
+//! Update from charge point to inform the Central System about the status of a signed firmware update.
+//!
+//! # Behaviour
+//! Part of the OCPP 1.6 Security Whitepaper. The Charge Point SHALL send SignedFirmwareStatusNotification.req PDUs to keep the Central
+//! System updated with the progress of a firmware update started with a [SignedUpdateFirmware.req](crate::server_init::signed_update_firmware).
+//! Compared to [FirmwareStatusNotification](crate::point_init::firmware_status_notification) the status is extended with the
+//! outcome of the signature verification and with scheduling information.
+//!
+//! The Charge Point SHALL only send the status Idle after receipt of an ExtendedTriggerMessage for a FirmwareStatusNotification,
+//! when it is not busy downloading/installing firmware.
+//!
+//! # Response
+//! Upon receipt of a SignedFirmwareStatusNotification.req PDU, the Central System SHALL respond with a SignedFirmwareStatusNotification.conf.
+
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use strum_macros::Display;
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/SignedFirmwareStatusNotification.json")]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the SignedFirmwareStatusNotification.req PDU sent by the Charge Point to the Central System.
+pub struct SignedFirmwareStatusNotificationRequest {
+    /// Required. This contains the progress status of the firmware installation.
+    pub status: SignedFirmwareStatus,
+    /// Optional. The request id that was provided in the SignedUpdateFirmware.req that started this firmware update. This field is
+    /// mandatory, unless the message was triggered by an ExtendedTriggerMessage.req and there is no firmware update ongoing.
+    pub request_id: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+/// Status of a signed firmware update as reported in SignedFirmwareStatusNotification.req.
+pub enum SignedFirmwareStatus {
+    /// Intermediate state. New firmware has been downloaded by Charge Point.
+    Downloaded,
+    /// Failure end state. Charge Point failed to download firmware.
+    DownloadFailed,
+    /// Intermediate state. Firmware is being downloaded.
+    Downloading,
+    /// Intermediate state. Downloading of new firmware has been scheduled.
+    DownloadScheduled,
+    /// Intermediate state. Downloading has been paused.
+    DownloadPaused,
+    /// Charge Point is not performing firmware update related tasks. Status Idle SHALL only be used as in a SignedFirmwareStatusNotification.req that was triggered by an ExtendedTriggerMessage.req.
+    Idle,
+    /// Failure end state. Installation of new firmware has failed.
+    InstallationFailed,
+    /// Intermediate state. Firmware is being installed.
+    Installing,
+    /// Successful end state. New firmware has successfully been installed in Charge Point.
+    Installed,
+    /// Intermediate state. Charge Point is about to reboot to activate new firmware.
+    InstallRebooting,
+    /// Intermediate state. Installation of the downloaded firmware is scheduled to take place on installDateTime given in SignedUpdateFirmware.req.
+    InstallScheduled,
+    /// Failure end state. Verification of the new firmware (e.g. using a checksum or some other means) has failed and installation will not proceed.
+    InstallVerificationFailed,
+    /// Failure end state. The firmware signature is not valid.
+    InvalidSignature,
+    /// Intermediate state. Provide signature successfully verified.
+    SignatureVerified,
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate(
+    "../json_schemas/SignedFirmwareStatusNotificationResponse.json"
+)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the SignedFirmwareStatusNotification.conf PDU sent by the Central System to the Charge Point in response to a SignedFirmwareStatusNotification.req PDU.
+pub struct SignedFirmwareStatusNotificationResponse {}
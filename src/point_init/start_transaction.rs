@@ -1,7 +1,7 @@
 //! Request from chargepoint to begin an energy transaction. Must be sent before charging will begin.
 //!
 //! # Behaviour
-//! If this transaction ends a reservation (see Reserve Now operation), then the StartTransaction.req MUST contain the reservationId.
+//! If this transaction ends a reservation (see [Reserve Now](crate::server_init::reserve_now) operation), then the StartTransaction.req MUST contain the reservationId.
 //! If Charge Point has implemented an Authorization Cache, then upon receipt of a StartTransaction.conf PDU the Charge Point SHALL update the cache
 //! entry, if the idTag is not in the Local Authorization List, with the IdTagInfo value from the response as described under Authorization Cache.
 //!  
@@ -36,6 +36,7 @@ pub struct StartTransactionRequest {
     /// Required. This contains the meter value in Wh for the connector at start of the transaction
     pub meter_start: i32,
     /// Optional. This contains the id of the reservation that terminates as a result of this transaction.
+    /// See [ReserveNowRequest::reservation_id](crate::server_init::reserve_now::ReserveNowRequest::reservation_id).
     pub reservation_id: Option<i32>,
     /// Required. This contains the date and time on which the transaction is started.
     pub timestamp: DateTime<Utc>,
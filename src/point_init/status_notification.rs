@@ -5,7 +5,7 @@
 //! 1. The Charge Point SHOULD send a StatusNotification.req PDU with its current status if the status changed while the Charge Point was offline.
 //! 2. The Charge Point MAY send a StatusNotification.req PDU to report an error that occurred while the Charge Point was offline.
 //! 3. The Charge Point SHOULD NOT send StatusNotification.req PDUs for historical status change events that happened while the Charge Point was offline
-//! and that do not inform the Central System of Charge Point errors or the Charge Point’s current status.
+//!    and that do not inform the Central System of Charge Point errors or the Charge Point’s current status.
 //! 4. The StatusNotification.req messages MUST be sent in the order in which the events that they describe occurred.
 //!
 //! To limit the number of transitions, the Charge Point MAY omit sending a StatusNotification.req if it was active for
@@ -111,7 +111,7 @@ pub enum StopReason {
     SoftReset,
     /// Central System sent an Unlock Connector command.
     UnlockCommand,
-    ///
+    /// The transaction was stopped because of the authorization status in a StartTransaction.conf.
     DeAuthorized,
 }
 
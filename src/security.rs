@@ -0,0 +1,272 @@
+//! OCPP 1.6 security profiles for the WebSocket transports
+//!
+//! Enabled with the `client` or `server` feature, with TLS additionally requiring the `tls` feature. The security profiles of
+//! the OCPP 1.6 security whitepaper are supported by both [CentralSystem](crate::server::CentralSystem) and
+//! [ChargePoint](crate::client::ChargePoint):
+//! 1. HTTP Basic authentication on an unencrypted connection. The user name is the charge point id and the password is the
+//!    [AuthorizationKey] of the charge point.
+//! 2. HTTP Basic authentication as in profile 1, on a TLS connection where the Central System presents a server certificate.
+//! 3. TLS with client certificates, where the common name of the charge point certificate is its charge point id.
+//!
+//! The Central System rejects the WebSocket handshake when the charge point id in the URL does not match the Basic
+//! authentication user name or the common name of the client certificate.
+//!
+//! # Example
+//! ```no_run
+//! # #[cfg(feature = "tls")]
+//! # fn ocpp_security_example() -> Result<(), Box<dyn std::error::Error>> {
+//! use ocpp::security::{self, AuthorizationKey};
+//!
+//! // Profile 2, configured on the Central System
+//! let server_config = security::server_tls_config(
+//!     &std::fs::read_to_string("server.pem")?,
+//!     &std::fs::read_to_string("server.key")?,
+//!     None,
+//! )?;
+//!
+//! // and on the charge point
+//! let client_config = security::client_tls_config(&std::fs::read_to_string("ca.pem")?, None)?;
+//! let key = AuthorizationKey::new("0123456789abcdef0123")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use thiserror::Error;
+#[cfg(feature = "tls")]
+pub use tokio_rustls::rustls;
+
+use crate::configuration::{
+    is_valid_authorization_key, MAX_AUTHORIZATION_KEY_LENGTH,
+    MIN_AUTHORIZATION_KEY_LENGTH,
+};
+use crate::ChangeConfigurationRequest;
+
+/// Name of the configuration key holding the HTTP Basic authentication password of a charge point
+pub const AUTHORIZATION_KEY: &str = "AuthorizationKey";
+
+/// Errors returned while setting up a security profile
+#[derive(Debug, Error)]
+pub enum SecurityError {
+    /// The authorization key is not 16 to 40 characters long, or contains characters other than printable ASCII
+    #[error("authorization key must be 16 to 40 printable ASCII characters")]
+    InvalidAuthorizationKey,
+    /// A certificate could not be decoded from PEM
+    #[error("invalid certificate: {0}")]
+    InvalidCertificate(String),
+    /// A private key could not be decoded from PEM
+    #[error("invalid private key: {0}")]
+    InvalidPrivateKey(String),
+    /// The TLS configuration was rejected
+    #[cfg(feature = "tls")]
+    #[error("tls error: {0}")]
+    Tls(#[from] rustls::Error),
+}
+
+/// Password of a charge point for HTTP Basic authentication, stored in the [AUTHORIZATION_KEY] configuration key
+///
+/// The key is write-only: a charge point SHALL NOT return it in a GetConfiguration.conf. It is therefore never printed by
+/// [Debug](fmt::Debug).
+#[derive(Clone, PartialEq, Eq)]
+pub struct AuthorizationKey(String);
+
+impl AuthorizationKey {
+    /// Minimum length of an authorization key
+    pub const MIN_LENGTH: usize = MIN_AUTHORIZATION_KEY_LENGTH;
+    /// Maximum length of an authorization key
+    pub const MAX_LENGTH: usize = MAX_AUTHORIZATION_KEY_LENGTH;
+
+    /// Create a key, checking its length and characters as [is_valid_authorization_key] does
+    pub fn new(key: impl Into<String>) -> Result<Self, SecurityError> {
+        let key = key.into();
+        if !is_valid_authorization_key(&key) {
+            return Err(SecurityError::InvalidAuthorizationKey);
+        }
+        Ok(AuthorizationKey(key))
+    }
+
+    /// The key as a string
+    pub fn as_str(&self) -> &str { &self.0 }
+
+    /// ChangeConfiguration.req setting this key on a charge point
+    pub fn change_configuration(&self) -> ChangeConfigurationRequest {
+        ChangeConfigurationRequest {
+            key: String::from(AUTHORIZATION_KEY),
+            value: self.0.clone(),
+        }
+    }
+
+    /// The key set by a ChangeConfiguration.req, or `None` if the request changes another configuration key
+    pub fn from_change_configuration(
+        req: &ChangeConfigurationRequest,
+    ) -> Option<Result<Self, SecurityError>> {
+        req.key
+            .eq_ignore_ascii_case(AUTHORIZATION_KEY)
+            .then(|| AuthorizationKey::new(&*req.value))
+    }
+
+    /// Compare with a received password in constant time
+    #[cfg(feature = "server")]
+    pub(crate) fn matches(&self, password: &str) -> bool {
+        self.0.len() == password.len()
+            && self
+                .0
+                .bytes()
+                .zip(password.bytes())
+                .fold(0, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
+impl FromStr for AuthorizationKey {
+    type Err = SecurityError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> { AuthorizationKey::new(s) }
+}
+
+impl fmt::Debug for AuthorizationKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AuthorizationKey(..)")
+    }
+}
+
+/// Value of the Authorization header sent by a charge point
+#[cfg(feature = "client")]
+pub(crate) fn basic_authorization(
+    charge_point_id: &str,
+    key: &AuthorizationKey,
+) -> String {
+    format!(
+        "Basic {}",
+        STANDARD.encode(format!("{}:{}", charge_point_id, key.as_str()))
+    )
+}
+
+/// User name and password of a Basic Authorization header
+#[cfg(feature = "server")]
+pub(crate) fn parse_basic_authorization(
+    value: &str,
+) -> Option<(String, String)> {
+    let (scheme, credentials) = value.trim().split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("basic") {
+        return None;
+    }
+    let decoded =
+        String::from_utf8(STANDARD.decode(credentials.trim()).ok()?).ok()?;
+    let (user, password) = decoded.split_once(':')?;
+    Some((String::from(user), String::from(password)))
+}
+
+/// Common name of a DER encoded certificate
+#[cfg(all(feature = "server", feature = "tls"))]
+pub(crate) fn common_name(certificate: &[u8]) -> Option<String> {
+    let (_, cert) = x509_parser::parse_x509_certificate(certificate).ok()?;
+    let common_name = cert.subject().iter_common_name().next()?;
+    common_name.as_str().ok().map(String::from)
+}
+
+/// TLS configuration for a Central System, from a PEM encoded certificate chain and private key (profile 2).
+///
+/// With `client_root_certificates`, charge points must present a client certificate issued by one of these PEM encoded
+/// certificates (profile 3).
+#[cfg(feature = "tls")]
+pub fn server_tls_config(
+    certificate_chain: &str,
+    private_key: &str,
+    client_root_certificates: Option<&str>,
+) -> Result<std::sync::Arc<rustls::ServerConfig>, SecurityError> {
+    use rustls::server::WebPkiClientVerifier;
+
+    let provider = provider();
+    let builder = rustls::ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+    let builder = match client_root_certificates {
+        Some(roots) => {
+            let verifier = WebPkiClientVerifier::builder_with_provider(
+                root_store(roots)?.into(),
+                provider,
+            )
+            .build()
+            .map_err(|e| SecurityError::InvalidCertificate(e.to_string()))?;
+            builder.with_client_cert_verifier(verifier)
+        },
+        None => builder.with_no_client_auth(),
+    };
+    let config = builder.with_single_cert(
+        certificates(certificate_chain)?,
+        key(private_key)?,
+    )?;
+    Ok(std::sync::Arc::new(config))
+}
+
+/// TLS configuration for a charge point trusting the PEM encoded `root_certificates` (profile 2).
+///
+/// With `client_certificate`, a PEM encoded certificate chain and private key, the charge point authenticates itself with a
+/// client certificate (profile 3).
+#[cfg(feature = "tls")]
+pub fn client_tls_config(
+    root_certificates: &str,
+    client_certificate: Option<(&str, &str)>,
+) -> Result<std::sync::Arc<rustls::ClientConfig>, SecurityError> {
+    let builder = rustls::ClientConfig::builder_with_provider(provider())
+        .with_safe_default_protocol_versions()?
+        .with_root_certificates(root_store(root_certificates)?);
+    let config = match client_certificate {
+        Some((certificate_chain, private_key)) => builder
+            .with_client_auth_cert(
+                certificates(certificate_chain)?,
+                key(private_key)?,
+            )?,
+        None => builder.with_no_client_auth(),
+    };
+    Ok(std::sync::Arc::new(config))
+}
+
+#[cfg(feature = "tls")]
+fn provider() -> std::sync::Arc<rustls::crypto::CryptoProvider> {
+    std::sync::Arc::new(rustls::crypto::ring::default_provider())
+}
+
+#[cfg(feature = "tls")]
+fn certificates(
+    pem: &str,
+) -> Result<Vec<rustls::pki_types::CertificateDer<'static>>, SecurityError> {
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::CertificateDer;
+
+    let certificates = CertificateDer::pem_slice_iter(pem.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| SecurityError::InvalidCertificate(e.to_string()))?;
+    if certificates.is_empty() {
+        return Err(SecurityError::InvalidCertificate(String::from(
+            "no certificate found",
+        )));
+    }
+    Ok(certificates)
+}
+
+#[cfg(feature = "tls")]
+fn key(
+    pem: &str,
+) -> Result<rustls::pki_types::PrivateKeyDer<'static>, SecurityError> {
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::PrivateKeyDer;
+
+    PrivateKeyDer::from_pem_slice(pem.as_bytes())
+        .map_err(|e| SecurityError::InvalidPrivateKey(e.to_string()))
+}
+
+#[cfg(feature = "tls")]
+fn root_store(pem: &str) -> Result<rustls::RootCertStore, SecurityError> {
+    let mut roots = rustls::RootCertStore::empty();
+    for certificate in certificates(pem)? {
+        roots
+            .add(certificate)
+            .map_err(|e| SecurityError::InvalidCertificate(e.to_string()))?;
+    }
+    Ok(roots)
+}
@@ -0,0 +1,445 @@
+//! WebSocket Central System server
+//!
+//! Enabled with the `server` feature. [CentralSystem] listens for charge point connections on `ws://host/ocpp/{chargePointId}`,
+//! negotiating the `ocpp1.6` subprotocol. Each connection is given its own [AsyncOCPPCallResultBuilder], created by a factory from
+//! the charge point id, which answers the calls sent by that charge point. Calls which are only sent by a Central System are
+//! rejected with [OCPPCallErrorCode::NotSupported](crate::OCPPCallErrorCode::NotSupported) without reaching the builder.
+//!
+//! Server initiated calls are sent through a [CentralSystemHandle], which awaits and decodes the response of the charge point.
+//!
+//! Charge points are authenticated according to the [security profiles](crate::security): with HTTP Basic authentication when
+//! [CentralSystem::basic_auth] is set, and over TLS, optionally with client certificates, when [CentralSystem::tls] is set.
+//!
+//! # Example
+//! ```no_run
+//! # async fn ocpp_server_example() -> Result<(), Box<dyn std::error::Error>> {
+//! use ocpp::server::CentralSystem;
+//! use ocpp::*;
+//!
+//! struct MyResultBuilder {
+//!     charge_point_id: String,
+//! }
+//! impl AsyncOCPPCallResultBuilder for MyResultBuilder {
+//!     async fn heartbeat(&self, _req: HeartbeatRequest) -> Result<HeartbeatResponse, OCPPCallErrorCode> {
+//!         println!("Heartbeat from {}", self.charge_point_id);
+//!         Ok(HeartbeatResponse { current_time: chrono::Utc::now() })
+//!     }
+//! }
+//!
+//! let server = CentralSystem::bind("0.0.0.0:9000", |charge_point_id: &str| MyResultBuilder {
+//!     charge_point_id: String::from(charge_point_id),
+//! })
+//! .await?;
+//! let handle = server.handle();
+//! tokio::spawn(server.run());
+//!
+//! // Later, once the charge point has connected
+//! let res = handle.call("CP001", ResetRequest { r#type: ResetType::Soft }).await?;
+//! println!("Reset: {}", res.status);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, ToSocketAddrs};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::handshake::server::{
+    ErrorResponse, Request, Response,
+};
+use tokio_tungstenite::tungstenite::http::{header, HeaderValue, StatusCode};
+
+use crate::connection::{self, Command, Dispatch};
+pub use crate::connection::{RequestError, OCPP_SUBPROTOCOL};
+use crate::security::{self, AuthorizationKey};
+use crate::{
+    AsyncOCPPCallResultBuilder, MessageIdGenerator, OCPPCall, OCPPCallError,
+    OCPPCallResult, OCPPRequest, UuidGenerator,
+};
+
+/// Default time to wait for a charge point to respond to a call
+pub const DEFAULT_CALL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Path prefix under which charge points connect
+const PATH_PREFIX: &str = "/ocpp/";
+
+/// Lookup of the authorization key of a charge point by its id
+type AuthorizationKeys = dyn Fn(&str) -> Option<AuthorizationKey> + Send + Sync;
+
+/// WebSocket listener accepting charge point connections. See the [module documentation](self).
+pub struct CentralSystem<F> {
+    listener: TcpListener,
+    factory: Arc<F>,
+    handle: CentralSystemHandle,
+    ids: Arc<dyn MessageIdGenerator>,
+    call_timeout: Duration,
+    authorization_keys: Option<Arc<AuthorizationKeys>>,
+    #[cfg(feature = "tls")]
+    tls: Option<tokio_rustls::TlsAcceptor>,
+}
+
+impl<F, B> CentralSystem<F>
+where
+    F: Fn(&str) -> B + Send + Sync + 'static,
+    B: AsyncOCPPCallResultBuilder + Send + 'static,
+{
+    /// Listen on `addr`. `factory` is called with the charge point id of each new connection to create its result builder.
+    pub async fn bind(
+        addr: impl ToSocketAddrs,
+        factory: F,
+    ) -> io::Result<Self> {
+        Ok(CentralSystem {
+            listener: TcpListener::bind(addr).await?,
+            factory: Arc::new(factory),
+            handle: CentralSystemHandle::default(),
+            ids: Arc::new(UuidGenerator),
+            call_timeout: DEFAULT_CALL_TIMEOUT,
+            authorization_keys: None,
+            #[cfg(feature = "tls")]
+            tls: None,
+        })
+    }
+
+    /// Set the time to wait for a charge point to respond to a call. Defaults to [DEFAULT_CALL_TIMEOUT].
+    pub fn call_timeout(mut self, call_timeout: Duration) -> Self {
+        self.call_timeout = call_timeout;
+        self
+    }
+
+    /// Set the generator of unique ids for server initiated calls. Defaults to [UuidGenerator].
+    pub fn id_generator(
+        mut self,
+        ids: impl MessageIdGenerator + 'static,
+    ) -> Self {
+        self.ids = Arc::new(ids);
+        self
+    }
+
+    /// Require HTTP Basic authentication (security profiles 1 and 2). `authorization_keys` is called with the charge point id of
+    /// each new connection and returns its [AuthorizationKey], or `None` to reject an unknown charge point.
+    pub fn basic_auth(
+        mut self,
+        authorization_keys: impl Fn(&str) -> Option<AuthorizationKey>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.authorization_keys = Some(Arc::new(authorization_keys));
+        self
+    }
+
+    /// Accept connections over TLS (security profile 2). When `config` verifies client certificates (security profile 3), the
+    /// common name of the certificate must match the charge point id. See [security::server_tls_config].
+    #[cfg(feature = "tls")]
+    pub fn tls(mut self, config: Arc<security::rustls::ServerConfig>) -> Self {
+        self.tls = Some(tokio_rustls::TlsAcceptor::from(config));
+        self
+    }
+
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Handle for sending calls to connected charge points
+    pub fn handle(&self) -> CentralSystemHandle { self.handle.clone() }
+
+    /// Accept connections until the listener fails
+    pub async fn run(self) -> io::Result<()> {
+        loop {
+            let (stream, addr) = self.listener.accept().await?;
+            tracing::debug!("OCPP Connection from {}", addr);
+            let session = Session {
+                factory: self.factory.clone(),
+                handle: self.handle.clone(),
+                ids: self.ids.clone(),
+                call_timeout: self.call_timeout,
+                authorization_keys: self.authorization_keys.clone(),
+                common_name: None,
+            };
+
+            #[cfg(feature = "tls")]
+            if let Some(acceptor) = self.tls.clone() {
+                tokio::spawn(async move {
+                    match acceptor.accept(stream).await {
+                        Ok(stream) => {
+                            let common_name = stream
+                                .get_ref()
+                                .1
+                                .peer_certificates()
+                                .and_then(|certificates| certificates.first())
+                                .and_then(|certificate| {
+                                    security::common_name(certificate)
+                                });
+                            Session {
+                                common_name,
+                                ..session
+                            }
+                            .serve(stream)
+                            .await
+                        },
+                        Err(e) => {
+                            tracing::warn!("OCPP TLS handshake failed: {}", e)
+                        },
+                    }
+                });
+                continue;
+            }
+            tokio::spawn(session.serve(stream));
+        }
+    }
+}
+
+/// Handle for sending server initiated calls to connected charge points. Cheap to clone.
+#[derive(Clone, Default)]
+pub struct CentralSystemHandle {
+    connections: Arc<Mutex<HashMap<String, Connection>>>,
+}
+
+struct Connection {
+    id: u64,
+    commands: mpsc::Sender<Command>,
+}
+
+impl CentralSystemHandle {
+    /// Ids of the currently connected charge points
+    pub fn connected(&self) -> Vec<String> {
+        self.lock().keys().cloned().collect()
+    }
+
+    /// Whether the charge point is currently connected
+    pub fn is_connected(&self, charge_point_id: &str) -> bool {
+        self.lock().contains_key(charge_point_id)
+    }
+
+    /// Send a call to a charge point and await its response
+    pub async fn call<R: OCPPRequest>(
+        &self,
+        charge_point_id: &str,
+        req: R,
+    ) -> Result<R::Response, RequestError> {
+        if !R::ACTION.is_sent_by_central_system() {
+            return Err(RequestError::WrongDirection(R::ACTION));
+        }
+
+        let commands = self
+            .lock()
+            .get(charge_point_id)
+            .map(|connection| connection.commands.clone())
+            .ok_or_else(|| {
+                RequestError::NotConnected(String::from(charge_point_id))
+            })?;
+        connection::send_call(&commands, req).await
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Connection>> {
+        self.connections.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Dispatches calls from a charge point to its result builder
+struct CentralSystemDispatch<B>(Arc<B>);
+
+impl<B: AsyncOCPPCallResultBuilder + Send + 'static> Dispatch
+    for CentralSystemDispatch<B>
+{
+    fn dispatch(
+        &mut self,
+        call: OCPPCall,
+    ) -> impl Future<Output = Result<OCPPCallResult, OCPPCallError>> + Send + 'static
+    {
+        let builder = self.0.clone();
+        async move {
+            if !call.payload.action().is_sent_by_charge_point() {
+                return Err(connection::not_supported(&call));
+            }
+            builder.build_response(call).await
+        }
+    }
+}
+
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(0);
+
+/// State of an accepted connection
+struct Session<F> {
+    factory: Arc<F>,
+    handle: CentralSystemHandle,
+    ids: Arc<dyn MessageIdGenerator>,
+    call_timeout: Duration,
+    authorization_keys: Option<Arc<AuthorizationKeys>>,
+    /// Common name of the client certificate, when connected over TLS with client certificates
+    common_name: Option<String>,
+}
+
+impl<F, B> Session<F>
+where
+    F: Fn(&str) -> B + Send + Sync + 'static,
+    B: AsyncOCPPCallResultBuilder + Send + 'static,
+{
+    /// Perform the WebSocket handshake and exchange messages until the connection closes
+    // The handshake callback signature is fixed by tungstenite
+    #[allow(clippy::result_large_err)]
+    async fn serve<S>(self, stream: S)
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut charge_point_id = None;
+        let ws = match tokio_tungstenite::accept_hdr_async(
+            stream,
+            |req: &Request, res: Response| {
+                self.negotiate(req, res, &mut charge_point_id)
+            },
+        )
+        .await
+        {
+            Ok(ws) => ws,
+            Err(e) => {
+                tracing::warn!("OCPP Handshake failed: {}", e);
+                return;
+            },
+        };
+        let Some(charge_point_id) = charge_point_id else {
+            return;
+        };
+        tracing::info!("OCPP Charge point {} connected", charge_point_id);
+
+        let id = NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed);
+        let (commands, mut receiver) = mpsc::channel(16);
+        // A reconnecting charge point replaces its previous connection
+        self.handle
+            .lock()
+            .insert(charge_point_id.clone(), Connection { id, commands });
+
+        let dispatch =
+            CentralSystemDispatch(Arc::new((self.factory)(&charge_point_id)));
+        if let Err(e) = connection::run(
+            ws,
+            &mut receiver,
+            self.ids.clone(),
+            self.call_timeout,
+            dispatch,
+        )
+        .await
+        {
+            tracing::warn!(
+                "OCPP Connection to {} failed: {}",
+                charge_point_id,
+                e
+            );
+        }
+
+        let mut connections = self.handle.lock();
+        if connections.get(&charge_point_id).map(|c| c.id) == Some(id) {
+            connections.remove(&charge_point_id);
+        }
+        tracing::info!("OCPP Charge point {} disconnected", charge_point_id);
+    }
+
+    /// Check the request path, credentials and subprotocol of a WebSocket handshake, recording the charge point id
+    #[allow(clippy::result_large_err)]
+    fn negotiate(
+        &self,
+        req: &Request,
+        mut res: Response,
+        charge_point_id: &mut Option<String>,
+    ) -> Result<Response, ErrorResponse> {
+        let id = req
+            .uri()
+            .path()
+            .strip_prefix(PATH_PREFIX)
+            .filter(|id| !id.is_empty() && !id.contains('/'))
+            .ok_or_else(|| {
+                reject(
+                    StatusCode::NOT_FOUND,
+                    "expected path /ocpp/{chargePointId}",
+                )
+            })?;
+        self.authenticate(req, id)?;
+
+        let offered = req
+            .headers()
+            .get_all(header::SEC_WEBSOCKET_PROTOCOL)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .any(|protocol| protocol.trim() == OCPP_SUBPROTOCOL);
+        if !offered {
+            return Err(reject(
+                StatusCode::BAD_REQUEST,
+                "subprotocol ocpp1.6 is required",
+            ));
+        }
+
+        res.headers_mut().insert(
+            header::SEC_WEBSOCKET_PROTOCOL,
+            HeaderValue::from_static(OCPP_SUBPROTOCOL),
+        );
+        *charge_point_id = Some(String::from(id));
+        Ok(res)
+    }
+
+    /// Check that the client certificate and Basic authentication credentials, where required, belong to the charge point
+    #[allow(clippy::result_large_err)]
+    fn authenticate(
+        &self,
+        req: &Request,
+        charge_point_id: &str,
+    ) -> Result<(), ErrorResponse> {
+        if let Some(common_name) = &self.common_name {
+            if common_name != charge_point_id {
+                tracing::warn!(
+                    "OCPP Certificate of {} used by charge point {}",
+                    common_name,
+                    charge_point_id
+                );
+                return Err(reject(
+                    StatusCode::FORBIDDEN,
+                    "certificate does not match charge point id",
+                ));
+            }
+        }
+
+        if let Some(authorization_keys) = &self.authorization_keys {
+            let authorized = req
+                .headers()
+                .get(header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(security::parse_basic_authorization)
+                .is_some_and(|(user, password)| {
+                    user == charge_point_id
+                        && authorization_keys(charge_point_id)
+                            .is_some_and(|key| key.matches(&password))
+                });
+            if !authorized {
+                tracing::warn!(
+                    "OCPP Charge point {} failed to authenticate",
+                    charge_point_id
+                );
+                let mut res = reject(
+                    StatusCode::UNAUTHORIZED,
+                    "invalid charge point credentials",
+                );
+                res.headers_mut().insert(
+                    header::WWW_AUTHENTICATE,
+                    HeaderValue::from_static("Basic realm=\"OCPP\""),
+                );
+                return Err(res);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn reject(status: StatusCode, reason: &str) -> ErrorResponse {
+    let mut res = ErrorResponse::new(Some(String::from(reason)));
+    *res.status_mut() = status;
+    res
+}
@@ -0,0 +1,42 @@
+//! Server request to cancel an existing reservation
+//!
+//! # Behaviour
+//! To cancel a reservation the Central System SHALL send a CancelReservation.req PDU to the Charge Point, containing the id of the
+//! reservation as provided in the original [ReserveNow.req](crate::server_init::reserve_now).
+//!
+//! # Response
+//! If the Charge Point has a reservation matching the reservationId in the request PDU, it SHALL return status 'Accepted'. Otherwise it
+//! SHALL return 'Rejected'.
+
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/CancelReservation.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the CancelReservation.req PDU sent by the Central System to the Charge Point.
+pub struct CancelReservationRequest {
+    /// Required. Id of the reservation to cancel.
+    pub reservation_id: i32,
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate("../json_schemas/CancelReservationResponse.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the CancelReservation.conf PDU sent by the Charge Point to the Central System in response to a CancelReservation.req PDU.
+pub struct CancelReservationResponse {
+    /// Required. This indicates the success or failure of the cancelling of a reservation by Central System.
+    pub status: CancelReservationStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+/// Status in CancelReservation.conf.
+pub enum CancelReservationStatus {
+    /// Reservation for the identifier has been cancelled.
+    Accepted,
+    /// Reservation could not be cancelled, because there is no reservation active for the identifier.
+    Rejected,
+}
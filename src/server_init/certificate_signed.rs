@@ -0,0 +1,39 @@
+//! Server request to install a newly signed Charge Point certificate
+//!
+//! # Behaviour
+//! Part of the OCPP 1.6 Security Whitepaper. After the Central System has had a certificate signing request from a
+//! [SignCertificate.req](crate::point_init::sign_certificate) signed by its Certificate Authority, it sends the resulting certificate
+//! chain to the Charge Point in a CertificateSigned.req.
+//!
+//! The Charge Point SHALL verify the validity of the signed certificate before installing it, and SHALL switch to the new certificate
+//! the next time it connects to the Central System.
+//!
+//! # Response
+//! Upon receipt of a CertificateSigned.req PDU, the Charge Point SHALL respond with a CertificateSigned.conf PDU indicating whether the
+//! certificate was accepted.
+
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+
+pub use crate::common_types::SimpleStatus;
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/CertificateSigned.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the CertificateSigned.req PDU sent by the Central System to the Charge Point.
+pub struct CertificateSignedRequest {
+    /// Required. The signed PEM encoded X.509 certificates. This can also contain the necessary sub CA certificates. The maximum size
+    /// of this field is limited by the configuration key CertificateSignedMaxChainSize.
+    pub certificate_chain: String,
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate("../json_schemas/CertificateSignedResponse.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the CertificateSigned.conf PDU sent by the Charge Point to the Central System in response to a CertificateSigned.req PDU.
+pub struct CertificateSignedResponse {
+    /// Required. Returns whether certificate signing has been accepted, otherwise rejected.
+    pub status: SimpleStatus,
+}
@@ -43,8 +43,7 @@ pub struct ChangeConfigurationResponse {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
-/// Status returned in response to ChangeAvailability.req.
-
+/// Status returned in response to ChangeConfiguration.req.
 pub enum ChangeConfigurationStatus {
     /// Configuration key is supported and setting has been changed.
     Accepted,
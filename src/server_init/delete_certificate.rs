@@ -0,0 +1,46 @@
+//! Server request for a ChargePoint to delete an installed certificate
+//!
+//! # Behaviour
+//! Part of the OCPP 1.6 Security Whitepaper. The Central System requests the Charge Point to delete an installed root certificate,
+//! identified by the [CertificateHashData] as returned in a [GetInstalledCertificateIds.conf](crate::server_init::get_installed_certificate_ids).
+//!
+//! # Response
+//! Upon receipt of a DeleteCertificate.req PDU, the Charge Point SHALL respond with a DeleteCertificate.conf PDU indicating whether the
+//! certificate was found and deleted.
+
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+pub use crate::common::certificate::CertificateHashData;
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/DeleteCertificate.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the DeleteCertificate.req PDU sent by the Central System to the Charge Point.
+pub struct DeleteCertificateRequest {
+    /// Required. Indicates the certificate of which deletion is requested.
+    pub certificate_hash_data: CertificateHashData,
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate("../json_schemas/DeleteCertificateResponse.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the DeleteCertificate.conf PDU sent by the Charge Point to the Central System in response to a DeleteCertificate.req PDU.
+pub struct DeleteCertificateResponse {
+    /// Required. Charge Point indicates if it can process the request.
+    pub status: DeleteCertificateStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+/// Status in DeleteCertificate.conf.
+pub enum DeleteCertificateStatus {
+    /// Normal successful completion (no errors).
+    Accepted,
+    /// Processing failure.
+    Failed,
+    /// Requested resource not found.
+    NotFound,
+}
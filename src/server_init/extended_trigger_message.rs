@@ -0,0 +1,57 @@
+//! Server request to trigger a message from a charge point, including Security Whitepaper messages
+//!
+//! # Behaviour
+//! Part of the OCPP 1.6 Security Whitepaper. ExtendedTriggerMessage.req behaves as [TriggerMessage.req](crate::server_init::trigger_message),
+//! but allows the Central System to additionally request the messages introduced by the whitepaper: LogStatusNotification and
+//! SignChargePointCertificate.
+//!
+//! When requested with FirmwareStatusNotification, a Charge Point that was updated with a SignedUpdateFirmware.req SHALL send a
+//! SignedFirmwareStatusNotification.req. The requested message is leading: if the specified connectorId is not relevant to the
+//! message, it should be ignored.
+//!
+//! # Response
+//! The Charge Point SHALL first send the ExtendedTriggerMessage.conf response, before sending the requested message. If the Charge
+//! Point does not support the requested message it SHALL respond with NotImplemented.
+
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use strum_macros::Display;
+
+pub use crate::server_init::trigger_message::TriggerMessageStatus;
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/ExtendedTriggerMessage.json")]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the ExtendedTriggerMessage.req PDU sent by the Central System to the Charge Point.
+pub struct ExtendedTriggerMessageRequest {
+    /// Required. Type of the message to be triggered.
+    pub requested_message: MessageTriggerEnumType,
+    /// Optional. Only filled in when request applies to a specific connector.
+    pub connector_id: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+#[allow(missing_docs)]
+/// Type of request to be triggered in an ExtendedTriggerMessage.req.
+pub enum MessageTriggerEnumType {
+    BootNotification,
+    LogStatusNotification,
+    FirmwareStatusNotification,
+    Heartbeat,
+    MeterValues,
+    SignChargePointCertificate,
+    StatusNotification,
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate("../json_schemas/ExtendedTriggerMessageResponse.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the ExtendedTriggerMessage.conf PDU sent by the Charge Point to the Central System in response to an ExtendedTriggerMessage.req PDU.
+pub struct ExtendedTriggerMessageResponse {
+    /// Required. Indicates whether the Charge Point will send the requested notification or not.
+    pub status: TriggerMessageStatus,
+}
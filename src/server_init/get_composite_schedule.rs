@@ -32,11 +32,11 @@ pub use crate::{ChargingRateUnit, ChargingSchedule, SimpleStatus};
 /// Field definition of the GetCompositeSchedule.req PDU sent by the Central System to the Charge Point.
 pub struct GetCompositeScheduleRequest {
     /// Required. The ID of the Connector for which the schedule is requested. When ConnectorId=0, the Charge Point will calculate the expected consumption for the grid connection.
-    connector_id: u32,
+    pub connector_id: u32,
     /// Required. Time in seconds. length of requested schedule
-    duration: u32,
+    pub duration: u32,
     /// Optional. Can be used to force a power or current profile
-    charging_rate_unit: Option<ChargingRateUnit>,
+    pub charging_rate_unit: Option<ChargingRateUnit>,
 }
 
 // -------------------------- RESPONSE --------------------------
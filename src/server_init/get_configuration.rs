@@ -21,8 +21,8 @@ use serde_with::skip_serializing_none;
 #[serde(rename_all = "camelCase")]
 /// Field definition of the GetConfiguration.req PDU sent by the Central System to the Charge Point.
 pub struct GetConfigurationRequest {
-    /// Optional. List of keys for which the configuration value is requested. Keys are separated by ','
-    pub key: Option<String>,
+    /// Optional. List of keys for which the configuration value is requested.
+    pub key: Option<Vec<String>>,
 }
 
 // -------------------------- RESPONSE --------------------------
@@ -4,6 +4,8 @@
 //! The Central System SHALL send a GetDiagnostics.req PDU for getting diagnostic information of a Charge Point with a
 //! location where the Charge Point MUST upload its diagnostic data to and optionally a begin and end time for the requested diagnostic information.
 //!
+//! See [GetLog](crate::server_init::get_log) for the Security Whitepaper's replacement of this flow, which also supports security logs.
+//!
 //! # Response
 //! Upon receipt of a GetDiagnostics.req PDU, and if diagnostics information is available then Charge Point SHALL respond
 //! with a GetDiagnostics.conf PDU stating the name of the file containing the diagnostic information that will be uploaded.
@@ -39,10 +41,11 @@ pub struct GetDiagnosticsRequest {
 
 // -------------------------- RESPONSE --------------------------
 #[json_validate("../json_schemas/GetDiagnosticsResponse.json")]
+#[skip_serializing_none]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 /// Field definition of the GetDiagnostics.conf PDU sent by the Charge Point to the Central System in response to a GetDiagnostics.req PDU.
 pub struct GetDiagnosticsResponse {
     /// Optional. This contains the name of the file with diagnostic information that will be uploaded. This field is not present when no diagnostic information is available.
-    pub file_name: String,
+    pub file_name: Option<String>,
 }
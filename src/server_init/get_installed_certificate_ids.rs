@@ -0,0 +1,48 @@
+//! Server request for a ChargePoint to report its installed root certificates
+//!
+//! # Behaviour
+//! Part of the OCPP 1.6 Security Whitepaper. To facilitate the management of the Charge Point’s installed certificates, the Central
+//! System can request the Charge Point to report the installed certificates of a given [CertificateUse].
+//!
+//! # Response
+//! Upon receipt of a GetInstalledCertificateIds.req PDU, the Charge Point SHALL respond with a GetInstalledCertificateIds.conf PDU
+//! containing the [CertificateHashData] of every matching certificate, or the status NotFound if none are installed.
+
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use strum_macros::Display;
+
+pub use crate::common::certificate::{CertificateHashData, CertificateUse};
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/GetInstalledCertificateIds.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the GetInstalledCertificateIds.req PDU sent by the Central System to the Charge Point.
+pub struct GetInstalledCertificateIdsRequest {
+    /// Required. Indicates the type of certificates requested.
+    pub certificate_type: CertificateUse,
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate("../json_schemas/GetInstalledCertificateIdsResponse.json")]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the GetInstalledCertificateIds.conf PDU sent by the Charge Point to the Central System in response to a GetInstalledCertificateIds.req PDU.
+pub struct GetInstalledCertificateIdsResponse {
+    /// Optional. The Charge Point includes the Certificate information for each available certificate.
+    pub certificate_hash_data: Option<Vec<CertificateHashData>>,
+    /// Required. Charge Point indicates if it can process the request.
+    pub status: GetInstalledCertificateStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+/// Status in GetInstalledCertificateIds.conf.
+pub enum GetInstalledCertificateStatus {
+    /// Normal successful completion (no errors).
+    Accepted,
+    /// Requested resource not found.
+    NotFound,
+}
@@ -0,0 +1,85 @@
+//! Server request for a ChargePoint to upload a diagnostics or security log
+//!
+//! # Behaviour
+//! Part of the OCPP 1.6 Security Whitepaper, extending the legacy [GetDiagnostics](crate::server_init::get_diagnostics) flow. The Central
+//! System can request a Charge Point to upload a file with either diagnostics or security logging information to the location given in
+//! the [LogParameters], optionally restricted to a time window.
+//!
+//! During uploading of the log file, the Charge Point MUST send [LogStatusNotification.req](crate::point_init::log_status_notification)
+//! PDUs, containing the requestId of this request, to keep the Central System updated with the status of the upload process.
+//!
+//! # Response
+//! Upon receipt of a GetLog.req PDU, the Charge Point SHALL respond with a GetLog.conf PDU stating the name of the file that will be
+//! uploaded. When a log upload is already ongoing, the Charge Point SHALL cancel it and respond with AcceptedCanceled if it accepts
+//! the new request.
+
+use chrono::{DateTime, Utc};
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use strum_macros::Display;
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/GetLog.json")]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the GetLog.req PDU sent by the Central System to the Charge Point.
+pub struct GetLogRequest {
+    /// Required. This field specifies the requested log and the location to which the log should be sent.
+    pub log: LogParameters,
+    /// Required. This contains the type of log file that the Charge Point should send.
+    pub log_type: LogType,
+    /// Required. The Id of this request.
+    pub request_id: i32,
+    /// Optional. This specifies how many times the Charge Point must try to upload the log before giving up. If this field is not present, it is left to Charge Point to decide how many times it wants to retry.
+    pub retries: Option<u32>,
+    /// Optional. The interval in seconds after which a retry may be attempted. If this field is not present, it is left to Charge Point to decide how long to wait between attempts.
+    pub retry_interval: Option<u32>,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Generic class for the configuration of logging entries, as used in GetLog.req.
+pub struct LogParameters {
+    /// Required. The URL of the location at the remote system where the log should be stored.
+    pub remote_location: String,
+    /// Optional. This contains the date and time of the oldest logging information to include in the diagnostics.
+    pub oldest_timestamp: Option<DateTime<Utc>>,
+    /// Optional. This contains the date and time of the latest logging information to include in the diagnostics.
+    pub latest_timestamp: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+/// Type of log requested in GetLog.req.
+pub enum LogType {
+    /// This contains the field definition of a diagnostics log file.
+    DiagnosticsLog,
+    /// Sent by the Central System to the Charge Point to request that the Charge Point uploads the security log.
+    SecurityLog,
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate("../json_schemas/GetLogResponse.json")]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the GetLog.conf PDU sent by the Charge Point to the Central System in response to a GetLog.req PDU.
+pub struct GetLogResponse {
+    /// Required. This field indicates whether the Charge Point was able to accept the request.
+    pub status: LogStatus,
+    /// Optional. This contains the name of the log file that will be uploaded. This field is not present when no logging information is available.
+    pub filename: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+/// Status in GetLog.conf.
+pub enum LogStatus {
+    /// Accepted this log upload. This does not mean the log file is uploaded is successfully, the Charge Point will now start the log file upload.
+    Accepted,
+    /// Log update request rejected.
+    Rejected,
+    /// Accepted this log upload, but in doing this has canceled an ongoing log file upload.
+    AcceptedCanceled,
+}
@@ -0,0 +1,52 @@
+//! Server request for a ChargePoint to install a new root certificate
+//!
+//! # Behaviour
+//! Part of the OCPP 1.6 Security Whitepaper. The Central System requests the Charge Point to install a new root certificate, either a
+//! Central System root certificate used to verify the Central System's TLS certificate or a Manufacturer root certificate used to verify
+//! signed firmware.
+//!
+//! The Charge Point SHALL attempt to verify the provided certificate before installing it. The Charge Point MAY limit the number of
+//! installed certificates, this limit is reported in the configuration key CertificateStoreMaxLength.
+//!
+//! # Response
+//! Upon receipt of an InstallCertificate.req PDU, the Charge Point SHALL respond with an InstallCertificate.conf PDU indicating whether
+//! the certificate was installed.
+
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+pub use crate::common::certificate::CertificateUse;
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/InstallCertificate.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the InstallCertificate.req PDU sent by the Central System to the Charge Point.
+pub struct InstallCertificateRequest {
+    /// Required. Indicates the certificate type that is sent.
+    pub certificate_type: CertificateUse,
+    /// Required. An X.509 certificate, first DER encoded into binary, and then Base64 encoded (PEM format).
+    pub certificate: String,
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate("../json_schemas/InstallCertificateResponse.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the InstallCertificate.conf PDU sent by the Charge Point to the Central System in response to an InstallCertificate.req PDU.
+pub struct InstallCertificateResponse {
+    /// Required. Charge Point indicates if installation was successful.
+    pub status: InstallCertificateStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+/// Status in InstallCertificate.conf.
+pub enum InstallCertificateStatus {
+    /// The installation of the certificate succeeded.
+    Accepted,
+    /// The certificate is valid and correct, but there is another reason the installation did not succeed.
+    Failed,
+    /// The certificate is invalid and/or incorrect OR the CA is not supported by the Charge Point.
+    Rejected,
+}
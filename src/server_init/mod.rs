@@ -1,34 +1,52 @@
 //! A collection of messages initiated by the central server
+pub mod cancel_reservation;
+pub mod certificate_signed;
 pub mod change_availability;
 pub mod change_configuration;
 pub mod clear_cache;
 pub mod clear_charging_profile;
+pub mod delete_certificate;
+pub mod extended_trigger_message;
 pub mod get_composite_schedule;
 pub mod get_configuration;
 pub mod get_diagnostics;
+pub mod get_installed_certificate_ids;
 pub mod get_local_list_version;
+pub mod get_log;
+pub mod install_certificate;
 pub mod remote_start_transaction;
 pub mod remote_stop_transaction;
+pub mod reserve_now;
 pub mod reset;
 pub mod send_local_list;
 pub mod set_charging_profile;
+pub mod signed_update_firmware;
 pub mod trigger_message;
 pub mod unlock_connector;
 pub mod update_firmware;
 
+pub use cancel_reservation::*;
+pub use certificate_signed::*;
 pub use change_availability::*;
 pub use change_configuration::*;
 pub use clear_cache::*;
 pub use clear_charging_profile::*;
+pub use delete_certificate::*;
+pub use extended_trigger_message::*;
 pub use get_composite_schedule::*;
 pub use get_configuration::*;
 pub use get_diagnostics::*;
+pub use get_installed_certificate_ids::*;
 pub use get_local_list_version::*;
+pub use get_log::*;
+pub use install_certificate::*;
 pub use remote_start_transaction::*;
 pub use remote_stop_transaction::*;
+pub use reserve_now::*;
 pub use reset::*;
 pub use send_local_list::*;
 pub use set_charging_profile::*;
+pub use signed_update_firmware::*;
 pub use trigger_message::*;
 pub use unlock_connector::*;
 pub use update_firmware::*;
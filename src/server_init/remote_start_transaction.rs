@@ -20,12 +20,12 @@
 //! The effect of the RemoteStartTransaction.req message depends on the value of the AuthorizeRemoteTxRequests configuration key in the Charge Point.
 //!
 //! * If the value of AuthorizeRemoteTxRequests is true, the Charge Point SHALL behave as if in response to a local action at the Charge Point
-//! to start a transaction with the idTag given in the RemoteStartTransaction.req message. This means that the Charge Point will first try to
-//! authorize the idTag, using the Local Authorization List, Authorization Cache and/or an Authorize.req request. A transaction will only be
-//! started after authorization was obtained.
+//!   to start a transaction with the idTag given in the RemoteStartTransaction.req message. This means that the Charge Point will first try to
+//!   authorize the idTag, using the Local Authorization List, Authorization Cache and/or an Authorize.req request. A transaction will only be
+//!   started after authorization was obtained.
 //! * If the value of AuthorizeRemoteTxRequests is false, the Charge Point SHALL immediately try to start a transaction for the idTag given in
-//! the RemoteStartTransaction.req message. Note that after the transaction has been started, the Charge Point will send a StartTransaction
-//! request to the Central System, and the Central System will check the authorization status of the idTag when processing this StartTransaction request.
+//!   the RemoteStartTransaction.req message. Note that after the transaction has been started, the Charge Point will send a StartTransaction
+//!   request to the Central System, and the Central System will check the authorization status of the idTag when processing this StartTransaction request.
 //!
 
 use ocpp_json_validate::json_validate;
@@ -0,0 +1,82 @@
+//! Server request to reserve a connector for a specific idTag
+//!
+//! # Behaviour
+//! A Central System can issue a ReserveNow.req to a Charge Point to reserve a connector for use by a specific idTag. The Central System
+//! MAY specify a connector to be reserved.
+//!
+//! If the reservationId in the request matches a reservation in the Charge Point, then the Charge Point SHALL replace that reservation
+//! with the new reservation in the request.
+//!
+//! If the Charge Point accepts the reservation request, then it SHALL refuse charging for all incoming idTags on the reserved connector,
+//! except when the incoming idTag or the parent idTag match the idTag or parent idTag of the reservation.
+//!
+//! When the configuration key ReserveConnectorZeroSupported is set to true the Charge Point supports reservations on connector 0. If the
+//! connectorId in the reservation request is 0, then the Charge Point SHALL NOT reserve a specific connector, but SHALL make sure that at
+//! any time during the validity of the reservation, one connector remains available for the reserved idTag.
+//!
+//! The reservation SHALL be terminated on the Charge Point when either
+//! 1. a transaction is started for the reserved idTag or parent idTag and on the reserved connector or any connector when the reserved
+//!    connectorId is 0, or
+//! 2. when the time specified in expiryDate is reached, or
+//! 3. when the Charge Point or connector are set to Faulted or Unavailable.
+//!
+//! If a transaction for the reserved idTag is started, then Charge Point SHALL send the reservationId in the
+//! [StartTransaction.req](crate::point_init::start_transaction) PDU to notify the Central System that the reservation is terminated.
+//!
+//! When a reservation expires, the Charge Point SHALL terminate the reservation and make the connector available. The Charge Point SHALL
+//! send a status notification to notify the Central System that the reserved connector is now available.
+//!
+//! # Response
+//! Upon receipt of a ReserveNow.req PDU, the Charge Point SHALL respond with a ReserveNow.conf PDU. If the reservationId does not match
+//! any reservation in the Charge Point, then the Charge Point SHALL return the status value 'Accepted' if it succeeds in reserving a
+//! connector, or the status describing why the reservation could not be made.
+
+use chrono::{DateTime, Utc};
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use strum_macros::Display;
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/ReserveNow.json")]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the ReserveNow.req PDU sent by the Central System to the Charge Point.
+pub struct ReserveNowRequest {
+    /// Required. This contains the id of the connector to be reserved. A value of 0 means that the reservation is not for a specific connector.
+    pub connector_id: u32,
+    /// Required. This contains the date and time when the reservation ends.
+    pub expiry_date: DateTime<Utc>,
+    /// Required. The identifier for which the Charge Point has to reserve a connector.
+    pub id_tag: String,
+    /// Optional. The parent idTag.
+    pub parent_id_tag: Option<String>,
+    /// Required. Unique id for this reservation.
+    pub reservation_id: i32,
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate("../json_schemas/ReserveNowResponse.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the ReserveNow.conf PDU sent by the Charge Point to the Central System in response to a ReserveNow.req PDU.
+pub struct ReserveNowResponse {
+    /// Required. This indicates the success or failure of the reservation.
+    pub status: ReservationStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+/// Status in ReserveNow.conf.
+pub enum ReservationStatus {
+    /// Reservation has been made.
+    Accepted,
+    /// Reservation has not been made, because connectors or specified connector are in a faulted state.
+    Faulted,
+    /// Reservation has not been made. All connectors or the specified connector are occupied.
+    Occupied,
+    /// Reservation has not been made. Charge Point is not configured to accept reservations.
+    Rejected,
+    /// Reservation has not been made, because connectors or specified connector are in an unavailable state.
+    Unavailable,
+}
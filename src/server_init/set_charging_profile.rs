@@ -41,15 +41,16 @@
 //! * The StartSchedule of the first chargingSchedulePeriod in a chargingSchedule SHALL always be 0
 //!
 //! * When recurrencyKind is used in combination with a chargingSchedule duration shorter than the recurrencyKind period, the Charge Point SHALL fall back to default behaviour after the chargingSchedule duration ends.
-//! This fall back means that the Charge Point SHALL use a ChargingProfile with a lower stackLevel if available.
-//! If no other ChargingProfile is available, the Charge Point SHALL allow charging as if no ChargingProfile is installed.
-//! If the chargingSchedulePeriod and/or duration is longer then the recurrence period duration, the remainder periods SHALL not be executed.
+//!   This fall back means that the Charge Point SHALL use a ChargingProfile with a lower stackLevel if available.
+//!   If no other ChargingProfile is available, the Charge Point SHALL allow charging as if no ChargingProfile is installed.
+//!   If the chargingSchedulePeriod and/or duration is longer then the recurrence period duration, the remainder periods SHALL not be executed.
 
 use ocpp_json_validate::json_validate;
 use serde::{Deserialize, Serialize};
 use strum_macros::Display;
 
-pub use crate::ChargingProfile;
+use crate::ChargingProfilePurpose;
+pub use crate::{ChargingProfile, ChargingProfileError};
 
 /* Structure
 ConnectorId u32
@@ -86,6 +87,25 @@ pub struct SetChargingProfileRequest {
     pub cs_charging_profiles: ChargingProfile,
 }
 
+impl SetChargingProfileRequest {
+    /// Check the profile with [ChargingProfile::check], and that a TxProfile names its transaction and is not set on
+    /// connector 0
+    pub fn check(&self) -> Result<(), ChargingProfileError> {
+        let profile = &self.cs_charging_profiles;
+        profile.check()?;
+        if profile.charging_profile_purpose == ChargingProfilePurpose::TxProfile
+        {
+            if self.connector_id == 0 {
+                return Err(ChargingProfileError::TxProfileOnConnectorZero);
+            }
+            if profile.transaction_id.is_none() {
+                return Err(ChargingProfileError::MissingTransactionId);
+            }
+        }
+        Ok(())
+    }
+}
+
 // -------------------------- RESPONSE --------------------------
 #[json_validate("../json_schemas/SetChargingProfileResponse.json")]
 #[derive(Serialize, Deserialize, Debug, Clone)]
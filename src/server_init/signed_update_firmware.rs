@@ -0,0 +1,85 @@
+//! Server request for a ChargePoint to download and install signed firmware
+//!
+//! # Behaviour
+//! Part of the OCPP 1.6 Security Whitepaper, replacing [UpdateFirmware](crate::server_init::update_firmware) for Charge Points which
+//! require signed firmware. The Central System sends a SignedUpdateFirmware.req containing the location of the firmware, the
+//! certificate with which it was signed and the signature itself.
+//!
+//! The Charge Point SHALL validate the signing certificate against its installed ManufacturerRootCertificate. If the certificate is
+//! invalid the Charge Point SHALL respond with InvalidCertificate and send a SecurityEventNotification.req with the security event
+//! InvalidFirmwareSigningCertificate.
+//!
+//! After downloading, the Charge Point SHALL verify the firmware signature before installation, reporting the outcome with a
+//! [SignedFirmwareStatusNotification.req](crate::point_init::signed_firmware_status_notification) of either SignatureVerified or
+//! InvalidSignature. With the `firmware-verify` feature enabled, [Firmware] provides methods to perform both checks offline.
+//!
+//! # Response
+//! Upon receipt of a SignedUpdateFirmware.req PDU, the Charge Point SHALL respond with a SignedUpdateFirmware.conf PDU. When a firmware
+//! update is already ongoing, the Charge Point SHALL cancel it and respond with AcceptedCanceled if it accepts the new request.
+
+use chrono::{DateTime, Utc};
+use ocpp_json_validate::json_validate;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use strum_macros::Display;
+
+// -------------------------- REQUEST ---------------------------
+#[json_validate("../json_schemas/SignedUpdateFirmware.json")]
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the SignedUpdateFirmware.req PDU sent by the Central System to the Charge Point.
+pub struct SignedUpdateFirmwareRequest {
+    /// Optional. This specifies how many times Charge Point must try to download the firmware before giving up. If this field is not
+    /// present, it is left to Charge Point to decide how many times it wants to retry.
+    pub retries: Option<u32>,
+    /// Optional. The interval in seconds after which a retry may be attempted. If this field is not present, it is left to Charge Point
+    /// to decide how long to wait between attempts.
+    pub retry_interval: Option<u32>,
+    /// Required. The Id of this request.
+    pub request_id: i32,
+    /// Required. Specifies the firmware to be updated on the Charge Point.
+    pub firmware: Firmware,
+}
+
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Represents a copy of the firmware that can be loaded/updated on the Charge Point, as used in SignedUpdateFirmware.req.
+pub struct Firmware {
+    /// Required. URI defining the origin of the firmware.
+    pub location: String,
+    /// Required. Date and time at which the firmware shall be retrieved.
+    pub retrieve_date_time: DateTime<Utc>,
+    /// Optional. Date and time at which the firmware shall be installed.
+    pub install_date_time: Option<DateTime<Utc>>,
+    /// Required. Certificate with which the firmware was signed. PEM encoded X.509 certificate.
+    pub signing_certificate: String,
+    /// Required. Base64 encoded firmware signature.
+    pub signature: String,
+}
+
+// -------------------------- RESPONSE --------------------------
+#[json_validate("../json_schemas/SignedUpdateFirmwareResponse.json")]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Field definition of the SignedUpdateFirmware.conf PDU sent by the Charge Point to the Central System in response to a SignedUpdateFirmware.req PDU.
+pub struct SignedUpdateFirmwareResponse {
+    /// Required. This field indicates whether the Charge Point was able to accept the request.
+    pub status: UpdateFirmwareStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Display, Clone)]
+/// Status in SignedUpdateFirmware.conf.
+pub enum UpdateFirmwareStatus {
+    /// Accepted this firmware update request. This does not mean the firmware update is successful, the Charge Point will now start the firmware update process.
+    Accepted,
+    /// Firmware update request rejected.
+    Rejected,
+    /// Accepted this firmware update request, but in doing this has canceled an ongoing firmware update.
+    AcceptedCanceled,
+    /// The certificate is invalid.
+    InvalidCertificate,
+    /// Failure end state. The Firmware Signing certificate has been revoked.
+    RevokedCertificate,
+}
@@ -18,6 +18,9 @@
 //! For example, a request for a statusNotification for connectorId 0 is a request for the status of the Charge Point. A request
 //! for a statusNotification without connectorId is a request for multiple statusNotifications: the notification for the Charge
 //! Point itself and a notification for each of its connectors.
+//!
+//! Messages introduced by the Security Whitepaper can only be requested with
+//! [ExtendedTriggerMessage.req](crate::server_init::extended_trigger_message).
 
 use ocpp_json_validate::json_validate;
 use serde::{Deserialize, Serialize};
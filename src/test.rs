@@ -146,3 +146,2380 @@ fn test_charge_point_builder() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(profile, example_profile);
     Ok(())
 }
+
+#[test]
+fn test_charging_profile_check() -> Result<(), Box<dyn std::error::Error>> {
+    use crate::charging_profile::*;
+    use crate::SetChargingProfileRequest;
+
+    let builder = ChargingProfile::builder(ChargingRateUnit::A)
+        .id(1)
+        .stack_level(0)
+        .add_period(0, 16.0, None);
+    let profile = builder.clone().add_period(600, 8.5, Some(1)).try_build()?;
+
+    assert_eq!(
+        builder.clone().clear_periods().try_build(),
+        Err(ChargingProfileError::NoPeriods)
+    );
+    assert_eq!(
+        builder.clone().clear_periods().add_period(60, 16.0, None).try_build(),
+        Err(ChargingProfileError::FirstPeriodNotAtZero(60))
+    );
+    assert_eq!(
+        builder
+            .clone()
+            .add_period(600, 8.0, None)
+            .add_period(600, 6.0, None)
+            .try_build(),
+        Err(ChargingProfileError::PeriodsNotAscending {
+            previous: 600,
+            start_period: 600,
+        })
+    );
+    assert_eq!(
+        builder.clone().add_period(600, 8.25, None).try_build(),
+        Err(ChargingProfileError::LimitNotMultipleOfTenth {
+            start_period: 600,
+            limit: 8.25,
+        })
+    );
+    assert!(builder.clone().add_period(600, 22080.1, None).try_build().is_ok());
+
+    let recurring = builder.clone().kind(ChargingProfileKind::Recurring);
+    assert_eq!(
+        recurring.clone().schedule_start(Utc::now()).try_build(),
+        Err(ChargingProfileError::MissingRecurrencyKind)
+    );
+    assert_eq!(
+        recurring.clone().recurrency_kind(RecurrencyKind::Daily).try_build(),
+        Err(ChargingProfileError::MissingStartSchedule)
+    );
+    assert_eq!(
+        recurring
+            .recurrency_kind(RecurrencyKind::Daily)
+            .schedule_start(Utc::now())
+            .schedule_duration(2 * 24 * 3600)
+            .try_build(),
+        Err(ChargingProfileError::DurationExceedsRecurrence {
+            duration: 2 * 24 * 3600,
+            recurrency_kind: RecurrencyKind::Daily,
+        })
+    );
+    assert_eq!(
+        builder
+            .clone()
+            .purpose(ChargingProfilePurpose::TxDefaultProfile)
+            .transaction_id(1)
+            .try_build(),
+        Err(ChargingProfileError::TransactionIdWithoutTxProfile(
+            ChargingProfilePurpose::TxDefaultProfile
+        ))
+    );
+
+    // A TxProfile set with SetChargingProfile.req applies to a transaction
+    let mut req = SetChargingProfileRequest {
+        connector_id: 1,
+        cs_charging_profiles: profile,
+    };
+    assert_eq!(req.check(), Err(ChargingProfileError::MissingTransactionId));
+    req.cs_charging_profiles.transaction_id = Some(42);
+    req.check()?;
+    req.connector_id = 0;
+    assert_eq!(
+        req.check(),
+        Err(ChargingProfileError::TxProfileOnConnectorZero)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_reserve_now_call() -> Result<(), Box<dyn std::error::Error>>
+{
+    let json = "[2,\"65:1\",\"ReserveNow\",{\"connectorId\":1,\"expiryDate\":\"2022-01-24T05:30:00Z\",\"idTag\":\"test\",\"reservationId\":42}]";
+    let value: crate::OCPPMessage = serde_json::from_str(json)?;
+
+    if let crate::OCPPMessage::Call(call) = value {
+        match call.payload {
+            crate::OCPPCallPayload::ReserveNow(ref req) => {
+                assert_eq!(req.reservation_id, 42);
+                req.validate()?;
+            },
+            _ => panic!("Expected ReserveNow payload"),
+        }
+        assert_eq!(serde_json::to_string(&call)?, json);
+    } else {
+        panic!("Expected Call");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_get_installed_certificate_ids_result(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let json = "[3,\"66:1\",{\"certificateHashData\":[{\"hashAlgorithm\":\"SHA256\",\"issuerNameHash\":\"abc\",\"issuerKeyHash\":\"def\",\"serialNumber\":\"1\"}],\"status\":\"Accepted\"}]";
+    let value: crate::OCPPMessage = serde_json::from_str(json)?;
+
+    if let crate::OCPPMessage::CallResultUnknown(unknown) = value {
+        let result = crate::OCPPCallResult::from_unknown(
+            &crate::OCPPCallAction::GetInstalledCertificateIds,
+            unknown,
+        )?;
+        result.payload.validate()?;
+
+        if let crate::OCPPCallResultPayload::GetInstalledCertificateIds(res) =
+            &result.payload
+        {
+            let hash_data = res.certificate_hash_data.as_ref().unwrap();
+            assert_eq!(
+                hash_data[0].hash_algorithm,
+                crate::HashAlgorithm::Sha256
+            );
+        } else {
+            panic!("Expected GetInstalledCertificateIds payload");
+        }
+
+        assert_eq!(serde_json::to_string(&result)?, json);
+    } else {
+        panic!("Expected CallResultUnknown");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_security_event_type() -> Result<(), Box<dyn std::error::Error>> {
+    use crate::point_init::security_event_notification::*;
+
+    let json = "{\"type\":\"FirmwareUpdated\",\"timestamp\":\"2022-01-24T04:30:50.621Z\"}";
+    let req: SecurityEventNotificationRequest = serde_json::from_str(json)?;
+    assert_eq!(req.r#type, SecurityEvent::FirmwareUpdated);
+    assert!(req.r#type.is_critical());
+    req.validate()?;
+
+    assert!(SecurityEvent::ReconfigurationOfSecurityParameters.is_critical());
+    assert!(!SecurityEvent::InvalidMessages.is_critical());
+
+    let json = "{\"type\":\"AcmeDoorOpened\",\"timestamp\":\"2022-01-24T04:30:50.621Z\"}";
+    let req: SecurityEventNotificationRequest = serde_json::from_str(json)?;
+    assert_eq!(
+        req.r#type,
+        SecurityEvent::Other(String::from("AcmeDoorOpened"))
+    );
+    assert!(!req.r#type.is_critical());
+    assert_eq!(
+        serde_json::to_string(&req)?,
+        "{\"type\":\"AcmeDoorOpened\",\"timestamp\":\"2022-01-24T04:30:50.621Z\"}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_get_diagnostics_result_without_file_name(
+) -> Result<(), Box<dyn std::error::Error>> {
+    let json = "[3,\"67:1\",{}]";
+    let value: crate::OCPPMessage = serde_json::from_str(json)?;
+
+    if let crate::OCPPMessage::CallResultUnknown(unknown) = value {
+        let result = crate::OCPPCallResult::from_unknown(
+            &crate::OCPPCallAction::GetDiagnostics,
+            unknown,
+        )?;
+        result.payload.validate()?;
+        assert_eq!(serde_json::to_string(&result)?, json);
+    } else {
+        panic!("Expected CallResultUnknown");
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "firmware-verify")]
+#[test]
+fn test_verify_signed_firmware() -> Result<(), Box<dyn std::error::Error>> {
+    use crate::firmware_verify::FirmwareVerifyError;
+    use crate::server_init::signed_update_firmware::Firmware;
+
+    let firmware = Firmware {
+        location: String::from("https://example.com/firmware.bin"),
+        retrieve_date_time: chrono::Utc::now(),
+        install_date_time: None,
+        signing_certificate: String::from(include_str!(
+            "test_data/firmware/signer.pem"
+        )),
+        signature: String::from(include_str!(
+            "test_data/firmware/firmware.sig"
+        )),
+    };
+    let image = include_bytes!("test_data/firmware/firmware.bin");
+
+    firmware.verify_signing_certificate(include_str!(
+        "test_data/firmware/root.pem"
+    ))?;
+    firmware.verify_signature(image)?;
+
+    assert!(matches!(
+        firmware.verify_signature(b"tampered firmware image\n"),
+        Err(FirmwareVerifyError::InvalidSignature)
+    ));
+    assert!(matches!(
+        firmware.verify_signing_certificate(include_str!(
+            "test_data/firmware/other_root.pem"
+        )),
+        Err(FirmwareVerifyError::UntrustedCertificate)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_extended_trigger_message_response(
+) -> Result<(), Box<dyn std::error::Error>> {
+    use crate::server_init::extended_trigger_message::*;
+    use crate::{OCPPCallErrorCode, OCPPCallResultBuilder};
+
+    struct Builder {}
+    impl OCPPCallResultBuilder for Builder {
+        fn extended_trigger_message(
+            &mut self,
+            req: ExtendedTriggerMessageRequest,
+        ) -> Result<ExtendedTriggerMessageResponse, OCPPCallErrorCode> {
+            let status = match req.requested_message {
+                MessageTriggerEnumType::FirmwareStatusNotification
+                | MessageTriggerEnumType::SignChargePointCertificate => {
+                    TriggerMessageStatus::Accepted
+                },
+                _ => TriggerMessageStatus::NotImplemented,
+            };
+            Ok(ExtendedTriggerMessageResponse { status })
+        }
+    }
+
+    let json = "[2,\"68:1\",\"ExtendedTriggerMessage\",{\"requestedMessage\":\"SignChargePointCertificate\"}]";
+    let call: crate::OCPPCall = serde_json::from_str(json)?;
+    let result = Builder {}
+        .build_response(call)
+        .expect("ExtendedTriggerMessage should be handled");
+    assert_eq!(
+        serde_json::to_string(&result)?,
+        "[3,\"68:1\",{\"status\":\"Accepted\"}]"
+    );
+
+    let json = "[2,\"68:2\",\"ExtendedTriggerMessage\",{\"requestedMessage\":\"MeterValues\",\"connectorId\":1}]";
+    let call: crate::OCPPCall = serde_json::from_str(json)?;
+    let result = Builder {}
+        .build_response(call)
+        .expect("ExtendedTriggerMessage should be handled");
+    assert_eq!(
+        serde_json::to_string(&result)?,
+        "[3,\"68:2\",{\"status\":\"NotImplemented\"}]"
+    );
+
+    // SignedFirmwareStatusNotification is requested with FirmwareStatusNotification, it is not a trigger of its own
+    let json = "[2,\"68:3\",\"ExtendedTriggerMessage\",{\"requestedMessage\":\"SignedFirmwareStatusNotification\"}]";
+    assert!(serde_json::from_str::<crate::OCPPCall>(json).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_request_response_pairing() -> Result<(), Box<dyn std::error::Error>> {
+    use crate::{
+        OCPPCallAction, OCPPCallPayload, OCPPCallResultUnknown, OCPPRequest,
+    };
+
+    fn decode<R: OCPPRequest>(
+        req: &R,
+        unknown: OCPPCallResultUnknown,
+    ) -> Result<R::Response, Box<dyn std::error::Error>> {
+        let payload: OCPPCallPayload = req.clone().into();
+        assert_eq!(payload.action(), R::ACTION);
+        let result = payload.get_result_from_call(unknown)?;
+        R::Response::try_from(result)
+            .map_err(|other| format!("unexpected result {}", other).into())
+    }
+
+    let req = crate::AuthorizeRequest {
+        id_tag: String::from("test"),
+    };
+    assert_eq!(crate::AuthorizeRequest::ACTION, OCPPCallAction::Authorize);
+
+    let json = "[3,\"69:1\",{\"idTagInfo\":{\"status\":\"Accepted\"}}]";
+    let unknown: OCPPCallResultUnknown = serde_json::from_str(json)?;
+    let res = decode(&req, unknown)?;
+    assert_eq!(res.id_tag_info.status, crate::AuthorizationStatus::Accepted);
+
+    let call: crate::OCPPCall = (String::from("69:2"), req.into()).into();
+    assert_eq!(
+        serde_json::to_string(&call)?,
+        "[2,\"69:2\",\"Authorize\",{\"idTag\":\"test\"}]"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_call_errors() {
+    use crate::{parse_call, OCPPCallError, OCPPCallErrorCode, ParseCallError};
+
+    let parse_error = |text: &str| -> OCPPCallError {
+        match parse_call(text) {
+            Err(ParseCallError::Invalid(err)) => err,
+            other => panic!("Expected invalid call, got {:?}", other),
+        }
+    };
+
+    let err = parse_error("[2,\"70:1\",\"Authorize\",{\"idTag\":");
+    assert_eq!(err.unique_id, "-1");
+    assert!(matches!(
+        err.error_code,
+        OCPPCallErrorCode::FormationViolation
+    ));
+
+    let err = parse_error("[2,\"70:2\",\"Authorize\"]");
+    assert_eq!(err.unique_id, "70:2");
+    assert!(matches!(
+        err.error_code,
+        OCPPCallErrorCode::FormationViolation
+    ));
+
+    let err = parse_error("[2,\"70:3\",\"Authorize\",{\"idTag\":5}]");
+    assert!(matches!(
+        err.error_code,
+        OCPPCallErrorCode::TypeConstraintViolation
+    ));
+
+    let err = parse_error("[2,\"70:4\",\"Authorize\",{}]");
+    assert!(matches!(
+        err.error_code,
+        OCPPCallErrorCode::OccurenceConstraintViolation
+    ));
+
+    let err = parse_error(
+        "[2,\"70:5\",\"Authorize\",{\"idTag\":\"abcdefghijklmnopqrstuvwxyz\"}]",
+    );
+    assert!(matches!(
+        err.error_code,
+        OCPPCallErrorCode::PropertyConstraintViolation
+    ));
+    assert_eq!(
+        err.error_details["errors"].as_array().map(Vec::len),
+        Some(1)
+    );
+
+    let err = parse_error("[2,\"70:6\",\"Reset\",{\"type\":\"Medium\"}]");
+    assert!(matches!(
+        err.error_code,
+        OCPPCallErrorCode::PropertyConstraintViolation
+    ));
+
+    let err = parse_error("[2,\"70:7\",\"Unknown\",{}]");
+    assert!(matches!(err.error_code, OCPPCallErrorCode::NotImplemented));
+    assert_eq!(err.error_details["action"], "Unknown");
+
+    assert!(parse_call("[2,\"70:8\",\"Heartbeat\",{}]").is_ok());
+
+    assert!(matches!(
+        parse_call("[3,\"70:9\",{}]"),
+        Err(ParseCallError::NotACall(3))
+    ));
+    assert!(matches!(
+        parse_call("[4,\"70:10\",\"GenericError\",\"\",{}]"),
+        Err(ParseCallError::NotACall(4))
+    ));
+}
+
+#[test]
+fn test_from_serde_error() {
+    use crate::{AuthorizeRequest, OCPPCallError, OCPPCallErrorCode};
+
+    let classify = |json: &str| {
+        let err = serde_json::from_str::<AuthorizeRequest>(json).unwrap_err();
+        let err = OCPPCallError::from_serde_error("73:1", &err);
+        assert_eq!(err.error_details, serde_json::json!({}));
+        err.error_code
+    };
+
+    // Pin the serde messages the classification relies on, so that a change of
+    // wording in serde_json fails here instead of downgrading the error code
+    let message = |json: &str| {
+        serde_json::from_str::<AuthorizeRequest>(json).unwrap_err().to_string()
+    };
+    let missing = message("{}");
+    assert!(
+        missing.starts_with("missing field"),
+        "serde_json changed the wording of a missing field: {}",
+        missing
+    );
+    let invalid_type = message("{\"idTag\":5}");
+    assert!(
+        invalid_type.starts_with("invalid type"),
+        "serde_json changed the wording of an invalid type: {}",
+        invalid_type
+    );
+    let unknown_variant =
+        serde_json::from_str::<crate::ResetRequest>("{\"type\":\"Medium\"}")
+            .unwrap_err()
+            .to_string();
+    assert!(
+        !unknown_variant.starts_with("missing field")
+            && !unknown_variant.starts_with("invalid type"),
+        "serde_json reports an unknown variant as another kind of error: {}",
+        unknown_variant
+    );
+
+    assert!(matches!(
+        classify("{\"idTag\":"),
+        OCPPCallErrorCode::FormationViolation
+    ));
+    assert!(matches!(
+        classify("{}"),
+        OCPPCallErrorCode::OccurenceConstraintViolation
+    ));
+    assert!(matches!(
+        classify("{\"idTag\":5}"),
+        OCPPCallErrorCode::TypeConstraintViolation
+    ));
+    let err =
+        serde_json::from_str::<crate::ResetRequest>("{\"type\":\"Medium\"}")
+            .unwrap_err();
+    assert!(matches!(
+        OCPPCallError::from_serde_error("73:2", &err).error_code,
+        OCPPCallErrorCode::PropertyConstraintViolation
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_async_call_result_builder(
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    use crate::{
+        parse_call, AsyncOCPPCallResultBuilder, OCPPCallErrorCode,
+        StartTransactionRequest, StartTransactionResponse,
+    };
+
+    struct Builder {
+        transaction_id: AtomicU32,
+    }
+    impl AsyncOCPPCallResultBuilder for Builder {
+        async fn start_transaction(
+            &self,
+            _req: StartTransactionRequest,
+        ) -> Result<StartTransactionResponse, OCPPCallErrorCode> {
+            tokio::task::yield_now().await;
+            let transaction_id =
+                self.transaction_id.fetch_add(1, Ordering::SeqCst);
+            Ok(StartTransactionResponse {
+                id_tag_info: crate::IdTagInfo {
+                    expiry_date: None,
+                    parent_id_tag: None,
+                    status: crate::AuthorizationStatus::Accepted,
+                },
+                transaction_id,
+            })
+        }
+    }
+
+    let builder = Arc::new(Builder {
+        transaction_id: AtomicU32::new(1),
+    });
+
+    let call = parse_call("[2,\"71:1\",\"StartTransaction\",{\"connectorId\":1,\"idTag\":\"test\",\"meterStart\":0,\"timestamp\":\"2022-01-24T04:30:50.621Z\"}]").expect("StartTransaction should parse");
+    let task_builder = builder.clone();
+    let result =
+        tokio::spawn(async move { task_builder.build_response(call).await })
+            .await?
+            .expect("StartTransaction should be handled");
+    assert_eq!(
+        serde_json::to_string(&result)?,
+        "[3,\"71:1\",{\"idTagInfo\":{\"status\":\"Accepted\"},\"transactionId\":1}]"
+    );
+
+    let call = parse_call("[2,\"71:2\",\"Heartbeat\",{}]")
+        .expect("Heartbeat should parse");
+    let err = builder.build_response(call).await.unwrap_err();
+    assert!(matches!(err.error_code, OCPPCallErrorCode::NotImplemented));
+
+    Ok(())
+}
+
+#[test]
+fn test_central_system_handler_direction() {
+    use crate::{
+        parse_call, CentralSystemHandler, HeartbeatRequest, HeartbeatResponse,
+        OCPPCallAction, OCPPCallErrorCode,
+    };
+
+    struct Handler {}
+    impl CentralSystemHandler for Handler {
+        fn heartbeat(
+            &mut self,
+            _req: HeartbeatRequest,
+        ) -> Result<HeartbeatResponse, OCPPCallErrorCode> {
+            Ok(HeartbeatResponse {
+                current_time: Utc::now(),
+            })
+        }
+    }
+
+    assert!(OCPPCallAction::Heartbeat.is_sent_by_charge_point());
+    assert!(!OCPPCallAction::Heartbeat.is_sent_by_central_system());
+    assert!(OCPPCallAction::DataTransfer.is_sent_by_charge_point());
+    assert!(OCPPCallAction::DataTransfer.is_sent_by_central_system());
+
+    let mut handler = Handler {};
+
+    let call = parse_call("[2,\"72:1\",\"Heartbeat\",{}]").unwrap();
+    assert!(handler.build_response(call).is_ok());
+
+    let call =
+        parse_call("[2,\"72:2\",\"Reset\",{\"type\":\"Hard\"}]").unwrap();
+    let err = handler.build_response(call).unwrap_err();
+    assert_eq!(err.unique_id, "72:2");
+    assert!(matches!(err.error_code, OCPPCallErrorCode::NotSupported));
+
+    let call =
+        parse_call("[2,\"72:3\",\"DataTransfer\",{\"vendorId\":\"test\"}]")
+            .unwrap();
+    let err = handler.build_response(call).unwrap_err();
+    assert!(matches!(err.error_code, OCPPCallErrorCode::NotImplemented));
+}
+
+#[test]
+fn test_pending_calls() -> Result<(), Box<dyn std::error::Error>> {
+    use std::time::{Duration, Instant};
+
+    use crate::{
+        HeartbeatRequest, OCPPCall, OCPPCallAction, OCPPCallResultPayload,
+        OCPPMessage, PendingCalls, PendingCallsError,
+    };
+
+    let mut pending = PendingCalls::new(Duration::from_secs(30));
+    let start = Instant::now();
+
+    let call: OCPPCall =
+        (String::from("73:1"), HeartbeatRequest {}.into()).into();
+    pending.push_at(&call, start)?;
+
+    let second: OCPPCall =
+        (String::from("73:2"), HeartbeatRequest {}.into()).into();
+    assert!(matches!(
+        pending.push_at(&second, start + Duration::from_secs(1)),
+        Err(PendingCallsError::CallOutstanding(id)) if id == "73:1"
+    ));
+
+    let json = "[3,\"73:9\",{\"currentTime\":\"2022-01-24T04:30:50.621Z\"}]";
+    if let OCPPMessage::CallResultUnknown(unknown) = serde_json::from_str(json)?
+    {
+        assert!(matches!(
+            pending.resolve_result_at(unknown, start + Duration::from_secs(2)),
+            Err(PendingCallsError::UnknownUniqueId(id)) if id == "73:9"
+        ));
+    } else {
+        panic!("Expected CallResultUnknown");
+    }
+
+    let json = "[3,\"73:1\",{\"currentTime\":\"2022-01-24T04:30:50.621Z\"}]";
+    if let OCPPMessage::CallResultUnknown(unknown) = serde_json::from_str(json)?
+    {
+        let result = pending
+            .resolve_result_at(unknown, start + Duration::from_secs(2))?;
+        assert!(matches!(
+            result.payload,
+            OCPPCallResultPayload::Heartbeat(_)
+        ));
+    } else {
+        panic!("Expected CallResultUnknown");
+    }
+    assert!(pending.outstanding().is_none());
+
+    pending.push_at(&second, start)?;
+    assert!(pending.expire_at(start + Duration::from_secs(29)).is_none());
+    let expired = pending.expire_at(start + Duration::from_secs(30));
+    assert_eq!(expired.map(|p| p.action), Some(OCPPCallAction::Heartbeat));
+    assert!(pending.outstanding().is_none());
+
+    // A response arriving after the timeout no longer matches the call
+    pending.push_at(&call, start)?;
+    let error = crate::OCPPCallError::new(
+        "73:1",
+        crate::OCPPCallErrorCode::GenericError,
+        "",
+        serde_json::json!({}),
+    );
+    assert!(matches!(
+        pending.resolve_error_at(&error, start + Duration::from_secs(30)),
+        Err(PendingCallsError::UnknownUniqueId(id)) if id == "73:1"
+    ));
+    pending.push_at(&call, start)?;
+    assert_eq!(
+        pending.resolve_error_at(&error, start + Duration::from_secs(29))?,
+        OCPPCallAction::Heartbeat
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_message_ids() {
+    use crate::{
+        validate_unique_id, CounterGenerator, MessageIdGenerator,
+        PrefixedCounterGenerator, UniqueIdError, UniqueIdValidator,
+        UuidGenerator,
+    };
+
+    let uuid = UuidGenerator.next_id();
+    assert_eq!(uuid.len(), 36);
+    assert_ne!(uuid, UuidGenerator.next_id());
+    assert!(validate_unique_id(&uuid).is_ok());
+
+    let counter = CounterGenerator::new(7);
+    assert_eq!(counter.next_id(), "7");
+    assert_eq!(counter.next_id(), "8");
+
+    let prefixed = PrefixedCounterGenerator::new("63").unwrap();
+    assert_eq!(prefixed.next_id(), "63:1");
+    assert_eq!(prefixed.next_id(), "63:2");
+
+    let longest = PrefixedCounterGenerator::new("a".repeat(15)).unwrap();
+    assert!(
+        validate_unique_id(&format!("{}:{}", "a".repeat(15), u64::MAX)).is_ok()
+    );
+    assert!(validate_unique_id(&longest.next_id()).is_ok());
+    assert!(matches!(
+        PrefixedCounterGenerator::new("a".repeat(16)),
+        Err(UniqueIdError::PrefixTooLong(_))
+    ));
+
+    assert_eq!(validate_unique_id(""), Err(UniqueIdError::Empty));
+    assert!(matches!(
+        validate_unique_id(&"a".repeat(37)),
+        Err(UniqueIdError::TooLong(_))
+    ));
+
+    let mut validator = UniqueIdValidator::new(2);
+    assert!(validator.check("1").is_ok());
+    assert!(validator.check("2").is_ok());
+    assert_eq!(
+        validator.check("1"),
+        Err(UniqueIdError::Duplicate(String::from("1")))
+    );
+    assert!(validator.check("3").is_ok());
+    // "1" has left the window and may be reused
+    assert!(validator.check("1").is_ok());
+}
+
+#[cfg(feature = "server")]
+#[tokio::test]
+async fn test_central_system_server() -> Result<(), Box<dyn std::error::Error>>
+{
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+    use tokio_tungstenite::tungstenite::Message;
+
+    use crate::server::{CentralSystem, RequestError};
+    use crate::{
+        AsyncOCPPCallResultBuilder, AuthorizeRequest, AuthorizeResponse,
+        HeartbeatRequest, HeartbeatResponse, OCPPCallErrorCode, ResetRequest,
+        ResetType, SimpleStatus,
+    };
+
+    struct Builder {
+        authorized: std::sync::Arc<tokio::sync::Notify>,
+    }
+    impl AsyncOCPPCallResultBuilder for Builder {
+        async fn authorize(
+            &self,
+            _req: AuthorizeRequest,
+        ) -> Result<AuthorizeResponse, OCPPCallErrorCode> {
+            self.authorized.notified().await;
+            Ok(AuthorizeResponse {
+                id_tag_info: crate::IdTagInfo {
+                    expiry_date: None,
+                    parent_id_tag: None,
+                    status: crate::AuthorizationStatus::Accepted,
+                },
+            })
+        }
+        async fn heartbeat(
+            &self,
+            _req: HeartbeatRequest,
+        ) -> Result<HeartbeatResponse, OCPPCallErrorCode> {
+            Ok(HeartbeatResponse {
+                current_time: Utc::now(),
+            })
+        }
+    }
+
+    let authorized = std::sync::Arc::new(tokio::sync::Notify::new());
+    let factory = {
+        let authorized = authorized.clone();
+        move |_: &str| Builder {
+            authorized: authorized.clone(),
+        }
+    };
+    let server = CentralSystem::bind("127.0.0.1:0", factory)
+        .await?
+        .id_generator(crate::PrefixedCounterGenerator::new("74")?);
+    let addr = server.local_addr()?;
+    let handle = server.handle();
+    tokio::spawn(server.run());
+
+    // The ocpp1.6 subprotocol is required
+    let url = format!("ws://{}/ocpp/CP1", addr);
+    assert!(tokio_tungstenite::connect_async(url.as_str()).await.is_err());
+
+    let mut request = url.as_str().into_client_request()?;
+    request.headers_mut().insert("Sec-WebSocket-Protocol", "ocpp1.6".parse()?);
+    let (mut ws, response) = tokio_tungstenite::connect_async(request).await?;
+    assert_eq!(response.headers()["Sec-WebSocket-Protocol"], "ocpp1.6");
+
+    ws.send(Message::Text(String::from("[2,\"cp:1\",\"Heartbeat\",{}]")))
+        .await?;
+    let text = ws.next().await.ok_or("closed")??.into_text()?;
+    assert!(text.starts_with("[3,\"cp:1\",{\"currentTime\":"));
+
+    // Calls only sent by a Central System are rejected
+    ws.send(Message::Text(String::from(
+        "[2,\"cp:2\",\"Reset\",{\"type\":\"Hard\"}]",
+    )))
+    .await?;
+    let text = ws.next().await.ok_or("closed")??.into_text()?;
+    assert!(text.starts_with("[4,\"cp:2\",\"NotSupported\","));
+
+    // A slow handler does not hold up the answer to a later call
+    ws.send(Message::Text(String::from(
+        "[2,\"cp:3\",\"Authorize\",{\"idTag\":\"test\"}]",
+    )))
+    .await?;
+    ws.send(Message::Text(String::from("[2,\"cp:4\",\"Heartbeat\",{}]")))
+        .await?;
+    let text = ws.next().await.ok_or("closed")??.into_text()?;
+    assert!(text.starts_with("[3,\"cp:4\","));
+    authorized.notify_one();
+    let text = ws.next().await.ok_or("closed")??.into_text()?;
+    assert_eq!(
+        text,
+        "[3,\"cp:3\",{\"idTagInfo\":{\"status\":\"Accepted\"}}]"
+    );
+
+    assert_eq!(handle.connected(), vec![String::from("CP1")]);
+    let reset = tokio::spawn({
+        let handle = handle.clone();
+        async move {
+            handle
+                .call(
+                    "CP1",
+                    ResetRequest {
+                        r#type: ResetType::Soft,
+                    },
+                )
+                .await
+        }
+    });
+    let text = ws.next().await.ok_or("closed")??.into_text()?;
+    assert_eq!(text, "[2,\"74:1\",\"Reset\",{\"type\":\"Soft\"}]");
+    ws.send(Message::Text(String::from(
+        "[3,\"74:1\",{\"status\":\"Accepted\"}]",
+    )))
+    .await?;
+    assert_eq!(reset.await??.status, SimpleStatus::Accepted);
+
+    assert!(matches!(
+        handle.call("CP2", crate::ClearCacheRequest {}).await,
+        Err(RequestError::NotConnected(_))
+    ));
+    assert!(matches!(
+        handle.call("CP1", HeartbeatRequest {}).await,
+        Err(RequestError::WrongDirection(_))
+    ));
+
+    Ok(())
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_backoff() {
+    use std::time::Duration;
+
+    use crate::client::Backoff;
+
+    let mut backoff =
+        Backoff::new(Duration::from_secs(1), Duration::from_secs(10));
+    for ceiling in [1, 2, 4, 8, 10, 10] {
+        let delay = backoff.next_delay();
+        assert!(delay >= Duration::from_secs(ceiling) / 2);
+        assert!(delay <= Duration::from_secs(ceiling));
+    }
+
+    backoff.reset();
+    assert!(backoff.next_delay() <= Duration::from_secs(1));
+}
+
+#[cfg(all(feature = "client", feature = "server"))]
+#[tokio::test]
+async fn test_charge_point_client() -> Result<(), Box<dyn std::error::Error>> {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::sync::mpsc;
+
+    use crate::client::ChargePoint;
+    use crate::server::CentralSystem;
+    use crate::*;
+
+    struct Builder {
+        heartbeats: mpsc::UnboundedSender<String>,
+        charge_point_id: String,
+    }
+    impl AsyncOCPPCallResultBuilder for Builder {
+        async fn boot_notification(
+            &self,
+            _req: BootNotificationRequest,
+        ) -> Result<BootNotificationResponse, OCPPCallErrorCode> {
+            Ok(BootNotificationResponse {
+                status: BootNotificationStatus::Accepted,
+                current_time: Utc::now(),
+                interval: 1,
+            })
+        }
+
+        async fn heartbeat(
+            &self,
+            _req: HeartbeatRequest,
+        ) -> Result<HeartbeatResponse, OCPPCallErrorCode> {
+            let _ = self.heartbeats.send(self.charge_point_id.clone());
+            Ok(HeartbeatResponse {
+                current_time: Utc::now(),
+            })
+        }
+    }
+
+    struct Handler {}
+    impl ChargePointHandler for Handler {
+        fn unlock_connector(
+            &mut self,
+            req: UnlockConnectorRequest,
+        ) -> Result<UnlockConnectorResponse, OCPPCallErrorCode> {
+            assert_eq!(req.connector_id, 1);
+            Ok(UnlockConnectorResponse {
+                status: UnlockConnectorStatus::Unlocked,
+            })
+        }
+    }
+
+    let (heartbeats, mut received) = mpsc::unbounded_channel();
+    let heartbeats = Arc::new(heartbeats);
+    let server = CentralSystem::bind("127.0.0.1:0", move |id: &str| Builder {
+        heartbeats: (*heartbeats).clone(),
+        charge_point_id: String::from(id),
+    })
+    .await?;
+    let addr = server.local_addr()?;
+    let central_system = server.handle();
+    tokio::spawn(server.run());
+
+    let boot_notification = BootNotificationRequest {
+        charge_point_vendor: String::from("Example"),
+        charge_point_model: String::from("Model 1"),
+        charge_point_serial_number: None,
+        charge_box_serial_number: None,
+        firmware_version: None,
+        iccid: None,
+        imsi: None,
+        meter_type: None,
+        meter_serial_number: None,
+    };
+    let charge_point = ChargePoint::new(
+        format!("ws://{}/ocpp/CP1", addr),
+        boot_notification,
+        Handler {},
+    );
+    let handle = charge_point.handle();
+    tokio::spawn(charge_point.run());
+
+    // Heartbeats follow the interval of the accepted boot notification
+    let id = tokio::time::timeout(Duration::from_secs(5), received.recv())
+        .await?
+        .ok_or("server stopped")?;
+    assert_eq!(id, "CP1");
+    assert!(handle.is_accepted());
+
+    let res = central_system
+        .call("CP1", UnlockConnectorRequest { connector_id: 1 })
+        .await?;
+    assert_eq!(res.status, UnlockConnectorStatus::Unlocked);
+
+    let res =
+        central_system.call("CP1", ClearCacheRequest {}).await.unwrap_err();
+    assert!(matches!(
+        res,
+        client::RequestError::Rejected(OCPPCallError {
+            error_code: OCPPCallErrorCode::NotImplemented,
+            ..
+        })
+    ));
+
+    let res = handle
+        .call(DataTransferRequest {
+            vendor_id: String::from("Example"),
+            message_id: None,
+            data: None,
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(res, client::RequestError::Rejected(_)));
+
+    Ok(())
+}
+
+#[cfg(feature = "client")]
+#[tokio::test]
+async fn test_charge_point_reconnect() -> Result<(), Box<dyn std::error::Error>>
+{
+    use std::time::Duration;
+
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{
+        Request, Response,
+    };
+    use tokio_tungstenite::tungstenite::{self, Message};
+
+    use crate::client::{Backoff, ChargePoint, RequestError};
+    use crate::*;
+
+    #[allow(clippy::result_large_err)]
+    async fn accept(
+        listener: &TcpListener,
+    ) -> Result<
+        tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>,
+        Box<dyn std::error::Error>,
+    > {
+        let (stream, _) = listener.accept().await?;
+        let ws = tokio_tungstenite::accept_hdr_async(
+            stream,
+            |_: &Request, mut response: Response| {
+                response.headers_mut().insert(
+                    "Sec-WebSocket-Protocol",
+                    "ocpp1.6".parse().unwrap(),
+                );
+                Ok(response)
+            },
+        )
+        .await?;
+        Ok(ws)
+    }
+
+    fn parse(message: Option<Result<Message, tungstenite::Error>>) -> OCPPCall {
+        let text = message.unwrap().unwrap().into_text().unwrap();
+        parse_call(&text).unwrap()
+    }
+
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let boot_notification = BootNotificationRequest {
+        charge_point_vendor: String::from("Example"),
+        charge_point_model: String::from("Model 1"),
+        charge_point_serial_number: None,
+        charge_box_serial_number: None,
+        firmware_version: None,
+        iccid: None,
+        imsi: None,
+        meter_type: None,
+        meter_serial_number: None,
+    };
+    struct Handler {}
+    impl ChargePointHandler for Handler {}
+    let charge_point = ChargePoint::new(
+        format!("ws://{}/ocpp/CP1", listener.local_addr()?),
+        boot_notification,
+        Handler {},
+    )
+    .backoff(Backoff::new(
+        Duration::from_millis(10),
+        Duration::from_millis(10),
+    ));
+    let handle = charge_point.handle();
+    tokio::spawn(charge_point.run());
+
+    let mut ws = accept(&listener).await?;
+    let boot = parse(ws.next().await);
+    assert!(matches!(boot.payload, OCPPCallPayload::BootNotification(_)));
+    let accepted = format!(
+        "[3,\"{}\",{{\"status\":\"Accepted\",\"currentTime\":\"2022-01-24T04:30:50.621Z\",\"interval\":300}}]",
+        boot.unique_id
+    );
+    ws.send(Message::Text(accepted)).await?;
+
+    // The first call is left unanswered, so the second stays queued
+    let data_transfer = |vendor_id: &str| DataTransferRequest {
+        vendor_id: String::from(vendor_id),
+        message_id: None,
+        data: None,
+    };
+    let first = tokio::spawn({
+        let handle = handle.clone();
+        let req = data_transfer("first");
+        async move { handle.call(req).await }
+    });
+    let sent = parse(ws.next().await);
+    assert!(matches!(sent.payload, OCPPCallPayload::DataTransfer(_)));
+    let queued = tokio::spawn({
+        let handle = handle.clone();
+        let req = data_transfer("queued");
+        async move { handle.call(req).await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    drop(ws);
+
+    let result = tokio::time::timeout(Duration::from_secs(5), first).await??;
+    assert!(matches!(result, Err(RequestError::ConnectionClosed)));
+    let result = tokio::time::timeout(Duration::from_secs(5), queued).await??;
+    assert!(matches!(result, Err(RequestError::ConnectionClosed)));
+
+    // The next connection starts with the BootNotification.req
+    let mut ws = accept(&listener).await?;
+    let boot = parse(ws.next().await);
+    assert!(matches!(boot.payload, OCPPCallPayload::BootNotification(_)));
+
+    Ok(())
+}
+
+#[cfg(any(feature = "client", feature = "server"))]
+#[test]
+fn test_authorization_key() {
+    use crate::security::{AuthorizationKey, SecurityError, AUTHORIZATION_KEY};
+    use crate::ChangeConfigurationRequest;
+
+    assert!(matches!(
+        AuthorizationKey::new("too short"),
+        Err(SecurityError::InvalidAuthorizationKey)
+    ));
+    assert!(AuthorizationKey::new("with spaces in the key").is_err());
+    assert!(AuthorizationKey::new("x".repeat(41)).is_err());
+
+    let key = AuthorizationKey::new("0123456789abcdef0123")
+        .expect("valid authorization key");
+    assert_eq!(format!("{:?}", key), "AuthorizationKey(..)");
+
+    let req = key.change_configuration();
+    assert_eq!(req.key, AUTHORIZATION_KEY);
+    let changed = AuthorizationKey::from_change_configuration(&req)
+        .expect("authorization key request")
+        .expect("valid authorization key");
+    assert_eq!(changed, key);
+    let lowercase = ChangeConfigurationRequest {
+        key: String::from("authorizationkey"),
+        value: req.value.clone(),
+    };
+    assert!(AuthorizationKey::from_change_configuration(&lowercase)
+        .is_some_and(|changed| changed.is_ok()));
+
+    let other = ChangeConfigurationRequest {
+        key: String::from("HeartbeatInterval"),
+        value: String::from("300"),
+    };
+    assert!(AuthorizationKey::from_change_configuration(&other).is_none());
+}
+
+#[cfg(all(feature = "client", feature = "server", feature = "tls"))]
+#[tokio::test]
+async fn test_security_profiles() -> Result<(), Box<dyn std::error::Error>> {
+    use std::time::Duration;
+
+    use rcgen::{
+        BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose,
+        IsCa, KeyPair,
+    };
+
+    use crate::client::{ChargePoint, ChargePointHandle};
+    use crate::security::{self, AuthorizationKey};
+    use crate::server::CentralSystem;
+    use crate::*;
+
+    struct Builder {}
+    impl AsyncOCPPCallResultBuilder for Builder {
+        async fn boot_notification(
+            &self,
+            _req: BootNotificationRequest,
+        ) -> Result<BootNotificationResponse, OCPPCallErrorCode> {
+            Ok(BootNotificationResponse {
+                status: BootNotificationStatus::Accepted,
+                current_time: Utc::now(),
+                interval: 300,
+            })
+        }
+    }
+
+    struct Handler {}
+    impl ChargePointHandler for Handler {}
+
+    async fn accepted(handle: &ChargePointHandle) {
+        while !handle.is_accepted() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    let boot_notification = BootNotificationRequest {
+        charge_point_vendor: String::from("Example"),
+        charge_point_model: String::from("Model 1"),
+        charge_point_serial_number: None,
+        charge_box_serial_number: None,
+        firmware_version: None,
+        iccid: None,
+        imsi: None,
+        meter_type: None,
+        meter_serial_number: None,
+    };
+    let charge_point = |url: String| {
+        ChargePoint::new(url, boot_notification.clone(), Handler {})
+    };
+
+    // Self-signed root with a server certificate and a client certificate for CP1
+    let ca_key = KeyPair::generate()?;
+    let mut ca_params = CertificateParams::new(Vec::new())?;
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    ca_params.distinguished_name.push(DnType::CommonName, "Test Root");
+    let ca = ca_params.self_signed(&ca_key)?;
+
+    let server_key = KeyPair::generate()?;
+    let server_params = CertificateParams::new(vec![
+        String::from("localhost"),
+        String::from("127.0.0.1"),
+    ])?;
+    let server_cert = server_params.signed_by(&server_key, &ca, &ca_key)?;
+
+    let client_key = KeyPair::generate()?;
+    let mut client_params = CertificateParams::new(Vec::new())?;
+    client_params.distinguished_name.push(DnType::CommonName, "CP1");
+    client_params.extended_key_usages =
+        vec![ExtendedKeyUsagePurpose::ClientAuth];
+    let client_cert = client_params.signed_by(&client_key, &ca, &ca_key)?;
+
+    let key = AuthorizationKey::new("0123456789abcdef0123")?;
+    let known_key = key.clone();
+    let authorization_keys =
+        move |id: &str| (id == "CP1").then(|| known_key.clone());
+
+    // Profile 1: Basic authentication without TLS
+    let server = CentralSystem::bind("127.0.0.1:0", |_: &str| Builder {})
+        .await?
+        .basic_auth(authorization_keys.clone());
+    let addr = server.local_addr()?;
+    tokio::spawn(server.run());
+
+    let mut unauthenticated = charge_point(format!("ws://{}/ocpp/CP1", addr));
+    assert!(unauthenticated.connect().await.is_err());
+    let mut wrong_id = charge_point(format!("ws://{}/ocpp/CP2", addr))
+        .authorization_key(key.clone());
+    assert!(wrong_id.connect().await.is_err());
+
+    let mut wrong_key = charge_point(format!("ws://{}/ocpp/CP1", addr))
+        .authorization_key(AuthorizationKey::new("fedcba9876543210fedc")?);
+    assert!(wrong_key.connect().await.is_err());
+    // A key changed by the Central System is used on the next connection
+    let handle = wrong_key.handle();
+    handle.set_authorization_key(key.clone());
+    tokio::spawn(wrong_key.run());
+    tokio::time::timeout(Duration::from_secs(5), accepted(&handle)).await?;
+
+    // Profile 2: Basic authentication over TLS
+    let server_config = security::server_tls_config(
+        &server_cert.pem(),
+        &server_key.serialize_pem(),
+        None,
+    )?;
+    let client_config = security::client_tls_config(&ca.pem(), None)?;
+    let server = CentralSystem::bind("127.0.0.1:0", |_: &str| Builder {})
+        .await?
+        .basic_auth(authorization_keys)
+        .tls(server_config);
+    let addr = server.local_addr()?;
+    tokio::spawn(server.run());
+
+    let mut plain = charge_point(format!("ws://{}/ocpp/CP1", addr))
+        .authorization_key(key.clone());
+    assert!(plain.connect().await.is_err());
+    let other_root = CertificateParams::new(Vec::new())?
+        .self_signed(&KeyPair::generate()?)?;
+    let untrusted = security::client_tls_config(&other_root.pem(), None)?;
+    let mut untrusted = charge_point(format!("wss://{}/ocpp/CP1", addr))
+        .authorization_key(key.clone())
+        .tls(untrusted);
+    assert!(untrusted.connect().await.is_err());
+
+    let secure =
+        charge_point(format!("wss://localhost:{}/ocpp/CP1", addr.port()))
+            .authorization_key(key.clone())
+            .tls(client_config.clone());
+    let handle = secure.handle();
+    tokio::spawn(secure.run());
+    tokio::time::timeout(Duration::from_secs(5), accepted(&handle)).await?;
+
+    // Profile 3: client certificates, whose common name must match the charge point id
+    let server_config = security::server_tls_config(
+        &server_cert.pem(),
+        &server_key.serialize_pem(),
+        Some(&ca.pem()),
+    )?;
+    let client_config = security::client_tls_config(
+        &ca.pem(),
+        Some((&client_cert.pem(), &client_key.serialize_pem())),
+    )?;
+    let server = CentralSystem::bind("127.0.0.1:0", |_: &str| Builder {})
+        .await?
+        .tls(server_config);
+    let addr = server.local_addr()?;
+    tokio::spawn(server.run());
+
+    let mut no_certificate = charge_point(format!("wss://{}/ocpp/CP1", addr))
+        .tls(security::client_tls_config(&ca.pem(), None)?);
+    assert!(no_certificate.connect().await.is_err());
+    let mut wrong_id = charge_point(format!("wss://{}/ocpp/CP2", addr))
+        .tls(client_config.clone());
+    assert!(wrong_id.connect().await.is_err());
+
+    let secure =
+        charge_point(format!("wss://{}/ocpp/CP1", addr)).tls(client_config);
+    let handle = secure.handle();
+    tokio::spawn(secure.run());
+    tokio::time::timeout(Duration::from_secs(5), accepted(&handle)).await?;
+
+    Ok(())
+}
+
+#[test]
+fn test_message_queue() -> Result<(), Box<dyn std::error::Error>> {
+    use std::time::Duration;
+
+    use crate::{
+        FileMessageQueue, HeartbeatRequest, MemoryMessageQueue, MessageQueue,
+        MeterValuesRequest, OCPPCall, StartTransactionRequest,
+        TransactionRetry,
+    };
+
+    fn exercise(queue: &mut impl MessageQueue) -> std::io::Result<()> {
+        let start: OCPPCall = (
+            String::from("1"),
+            StartTransactionRequest {
+                connector_id: 1,
+                id_tag: String::from("TAG"),
+                meter_start: 0,
+                reservation_id: None,
+                timestamp: Utc::now(),
+            }
+            .into(),
+        )
+            .into();
+        let meter_values = |unique_id: &str, transaction_id| -> OCPPCall {
+            (
+                String::from(unique_id),
+                MeterValuesRequest {
+                    connector_id: 1,
+                    transaction_id,
+                    meter_value: Vec::new(),
+                }
+                .into(),
+            )
+                .into()
+        };
+
+        assert!(queue.enqueue(start)?);
+        assert!(!queue.enqueue(meter_values("2", None))?);
+        assert!(queue.enqueue(meter_values("3", Some(1)))?);
+        assert!(!queue
+            .enqueue((String::from("4"), HeartbeatRequest {}.into()).into())?);
+        assert_eq!(queue.len(), 2);
+        Ok(())
+    }
+
+    let retry = TransactionRetry::from_configuration("2", "60")
+        .ok_or("invalid configuration")?;
+    assert_eq!(retry.delay(2), Duration::from_secs(120));
+    let now = Utc::now();
+
+    let mut memory = MemoryMessageQueue::default();
+    exercise(&mut memory)?;
+    assert_eq!(
+        memory.next_due(now).map(|c| c.unique_id.as_str()),
+        Some("1")
+    );
+    // A failed message holds back the rest of the queue until its retry interval has passed
+    assert!(memory.failed(&retry, now)?.is_none());
+    assert!(memory.next_due(now).is_none());
+    let later = now + chrono::Duration::seconds(60);
+    assert_eq!(
+        memory.next_due(later).map(|c| c.unique_id.as_str()),
+        Some("1")
+    );
+    // and is discarded once it has used all its attempts
+    let discarded = memory.failed(&retry, later)?.ok_or("not discarded")?;
+    assert_eq!(discarded.call.unique_id, "1");
+    assert_eq!(
+        memory.next_due(later).map(|c| c.unique_id.as_str()),
+        Some("3")
+    );
+    memory.delivered()?;
+    assert!(memory.is_empty());
+
+    let path = std::env::temp_dir()
+        .join(format!("ocpp-message-queue-{}.log", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut file = FileMessageQueue::open(&path)?;
+    exercise(&mut file)?;
+    file.failed(&retry, now)?;
+    drop(file);
+
+    // Messages and failed attempts survive a restart
+    let mut file = FileMessageQueue::open(&path)?;
+    assert_eq!(file.len(), 2);
+    assert!(file.next_due(now).is_none());
+    assert!(file.failed(&retry, later)?.is_some());
+    drop(file);
+
+    let mut file = FileMessageQueue::open(&path)?;
+    assert_eq!(file.len(), 1);
+    assert_eq!(file.next_due(now).map(|c| c.unique_id.as_str()), Some("3"));
+    file.delivered()?;
+    assert!(file.is_empty());
+    assert_eq!(std::fs::metadata(file.path())?.len(), 0);
+    std::fs::remove_file(&path)?;
+
+    // Compaction leaves files sharing the stem of the log alone
+    let sibling = path.with_extension("tmp");
+    std::fs::write(&sibling, "unrelated")?;
+    let mut file = FileMessageQueue::open(&path)?;
+    exercise(&mut file)?;
+    drop(file);
+    let file = FileMessageQueue::open(&path)?;
+    assert_eq!(file.len(), 2);
+    assert_eq!(std::fs::read_to_string(&sibling)?, "unrelated");
+    std::fs::remove_file(&sibling)?;
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_connector_state() -> Result<(), Box<dyn std::error::Error>> {
+    use crate::StatusNotificationStatus::*;
+    use crate::{
+        ConnectorState, ConnectorStateError, StatusNotificationErrorCode,
+    };
+
+    let mut connector = ConnectorState::new(1);
+    connector.transition(Preparing)?;
+    connector.transition(Charging)?;
+    connector.transition(SuspendedEV)?;
+    connector.transition(Finishing)?;
+    connector.transition(Available)?;
+
+    let err = connector.transition(Finishing).unwrap_err();
+    assert_eq!(
+        err,
+        ConnectorStateError::IllegalTransition {
+            transition: String::from("A6"),
+            from: Available,
+            to: Finishing,
+        }
+    );
+    assert_eq!(connector.status(), &Available);
+    assert!(ConnectorState::is_allowed(1, &Faulted, &Reserved));
+    assert!(!ConnectorState::is_allowed(1, &Reserved, &Charging));
+
+    let mut report = connector.status_notification();
+    report.status = Faulted;
+    report.error_code = StatusNotificationErrorCode::GroundFailure;
+    report.vendor_error_code = Some(String::from("E42"));
+    connector.apply(&report)?;
+    report.status = Available;
+    report.error_code = StatusNotificationErrorCode::NoError;
+    report.vendor_error_code = None;
+    connector.apply(&report)?;
+    assert_eq!(
+        connector.error_code(),
+        &StatusNotificationErrorCode::NoError
+    );
+    let errors: Vec<_> = connector.error_history().collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].vendor_error_code.as_deref(), Some("E42"));
+
+    report.connector_id = 2;
+    assert!(matches!(
+        connector.apply(&report),
+        Err(ConnectorStateError::WrongConnector {
+            expected: 1,
+            actual: 2
+        })
+    ));
+
+    let mut main_controller = ConnectorState::new(0);
+    main_controller.transition(Faulted)?;
+    main_controller.transition(Unavailable)?;
+    assert_eq!(
+        main_controller.transition(Charging),
+        Err(ConnectorStateError::InvalidMainControllerStatus(Charging))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_transaction_tracker() {
+    use chrono::Duration;
+
+    use crate::{
+        MeterValue, MeterValuesRequest, SampledContext, SampledUnit,
+        SampledValue, StartTransactionRequest, StopReason,
+        StopTransactionRequest, TransactionAnomaly, TransactionTracker,
+    };
+
+    let started_at = Utc::now();
+    let sample = |minutes, value: &str, context| MeterValue {
+        timestamp: started_at + Duration::minutes(minutes),
+        sampled_value: vec![SampledValue {
+            value: String::from(value),
+            context,
+            format: None,
+            measurand: None,
+            phase: None,
+            location: None,
+            unit: Some(SampledUnit::KWh),
+        }],
+    };
+
+    let mut tracker = TransactionTracker::default();
+    let start = StartTransactionRequest {
+        connector_id: 1,
+        id_tag: String::from("TAG"),
+        meter_start: 1000,
+        reservation_id: None,
+        timestamp: started_at,
+    };
+    assert!(tracker.start(7, &start).is_empty());
+    assert!(tracker.start(7, &start).is_empty());
+    assert_eq!(
+        tracker.start(8, &start),
+        vec![TransactionAnomaly::ConnectorInUse {
+            connector_id: 1,
+            transaction_id: 8,
+            active: 7,
+        }]
+    );
+    tracker.remove(8);
+
+    // Samples arriving out of order are kept in time order
+    for minutes in [20, 10, 20] {
+        let req = MeterValuesRequest {
+            connector_id: 1,
+            transaction_id: Some(7),
+            meter_value: vec![sample(minutes, &format!("{}.5", minutes), None)],
+        };
+        assert!(tracker.meter_values(&req).is_empty());
+    }
+    let transaction = tracker.get(7).expect("transaction 7");
+    assert_eq!(transaction.meter_values.len(), 2);
+    assert_eq!(transaction.energy_delivered(), Some(19_500));
+    assert_eq!(
+        tracker.active_on_connector(1).map(|t| t.transaction_id),
+        Some(7)
+    );
+
+    let mut stop = StopTransactionRequest {
+        id_tag: Some(String::from("TAG")),
+        meter_stop: 25_000,
+        timestamp: started_at + Duration::minutes(30),
+        transaction_id: 7,
+        reason: Some(StopReason::Local),
+        transaction_data: Some(vec![sample(
+            30,
+            "25",
+            Some(SampledContext::TransactionEnd),
+        )]),
+    };
+    assert_eq!(
+        tracker.stop(&stop),
+        vec![TransactionAnomaly::MissingTransactionBegin(7)]
+    );
+    // A repeated stop is ignored
+    assert!(tracker.stop(&stop).is_empty());
+    let transaction = tracker.get(7).expect("transaction 7");
+    assert!(!transaction.is_active());
+    assert_eq!(transaction.meter_values.len(), 3);
+    assert_eq!(transaction.energy_delivered(), Some(24_000));
+
+    stop.meter_stop = 26_000;
+    assert_eq!(
+        tracker.stop(&stop),
+        vec![TransactionAnomaly::ConflictingStop(7)]
+    );
+    stop.transaction_id = 9;
+    assert_eq!(
+        tracker.stop(&stop),
+        vec![TransactionAnomaly::UnknownTransaction(9)]
+    );
+
+    assert!(tracker.start(10, &start).is_empty());
+    stop.transaction_id = 10;
+    stop.meter_stop = 500;
+    stop.transaction_data = None;
+    assert_eq!(
+        tracker.stop(&stop),
+        vec![TransactionAnomaly::MeterStopBelowMeterStart {
+            transaction_id: 10,
+            meter_start: 1000,
+            meter_stop: 500,
+        }]
+    );
+}
+
+#[test]
+fn test_transaction_id_allocator() -> std::io::Result<()> {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        MemoryTransactionIdAllocator, StartTransactionKey,
+        StartTransactionRequest, StoredTransactionIdAllocator,
+        TransactionIdAllocator, TransactionIdStore,
+    };
+
+    #[derive(Default)]
+    struct Store {
+        high_water_mark: Mutex<u32>,
+        answers: Mutex<HashMap<StartTransactionKey, u32>>,
+    }
+    impl TransactionIdStore for Arc<Store> {
+        fn high_water_mark(&self) -> std::io::Result<u32> {
+            Ok(*self.high_water_mark.lock().unwrap())
+        }
+
+        fn set_high_water_mark(&self, id: u32) -> std::io::Result<()> {
+            *self.high_water_mark.lock().unwrap() = id;
+            Ok(())
+        }
+
+        fn answered(
+            &self,
+            key: &StartTransactionKey,
+        ) -> std::io::Result<Option<u32>> {
+            Ok(self.answers.lock().unwrap().get(key).copied())
+        }
+
+        fn record_answer(
+            &self,
+            key: &StartTransactionKey,
+            id: u32,
+        ) -> std::io::Result<()> {
+            self.answers.lock().unwrap().insert(key.clone(), id);
+            Ok(())
+        }
+    }
+
+    let start = |connector_id| StartTransactionRequest {
+        connector_id,
+        id_tag: String::from("TAG"),
+        meter_start: 0,
+        reservation_id: None,
+        timestamp: Utc::now(),
+    };
+    let first = StartTransactionKey::new("CP1", &start(1));
+    let second = StartTransactionKey::new("CP1", &start(2));
+    let other_charge_point = StartTransactionKey {
+        charge_point_id: String::from("CP2"),
+        ..first.clone()
+    };
+
+    let memory = MemoryTransactionIdAllocator::default();
+    assert_eq!(memory.allocate(&first)?, 1);
+    assert_eq!(memory.allocate(&second)?, 2);
+    // A resent StartTransaction.req gets the id it was already given
+    assert_eq!(memory.allocate(&first)?, 1);
+    assert_eq!(memory.allocate(&other_charge_point)?, 3);
+
+    let store = Arc::new(Store::default());
+    let stored =
+        StoredTransactionIdAllocator::new(store.clone())?.block_size(10);
+    assert_eq!(stored.allocate(&first)?, 1);
+    assert_eq!(stored.allocate(&second)?, 2);
+    assert_eq!(*store.high_water_mark.lock().unwrap(), 10);
+
+    // After a restart, ids continue above the reserved block and answers are remembered
+    let restarted = StoredTransactionIdAllocator::new(store.clone())?;
+    assert_eq!(restarted.allocate(&first)?, 1);
+    assert_eq!(restarted.allocate(&other_charge_point)?, 11);
+    assert_eq!(*store.high_water_mark.lock().unwrap(), 110);
+
+    Ok(())
+}
+
+#[test]
+fn test_local_auth_list() {
+    use crate::{
+        AuthorizationStatus, IdTagInfo, LocalAuthList, LocalAuthorizationList,
+        SendLocalListRequest, UpdateStatus, UpdateType,
+    };
+
+    let info = |status| IdTagInfo {
+        expiry_date: None,
+        parent_id_tag: None,
+        status,
+    };
+    let entry = |id_tag: &str, id_tag_info| LocalAuthorizationList {
+        id_tag: String::from(id_tag),
+        id_tag_info,
+    };
+    let update = |list_version, update_type, entries| SendLocalListRequest {
+        list_version,
+        local_authorization_list: Some(entries),
+        update_type,
+    };
+
+    let mut list = LocalAuthList::default()
+        .local_auth_list_max_length(3)
+        .send_local_list_max_length(2);
+    assert_eq!(list.get_local_list_version().list_version, 0);
+
+    let full = update(
+        5,
+        UpdateType::Full,
+        vec![
+            entry("A", Some(info(AuthorizationStatus::Accepted))),
+            entry("B", Some(info(AuthorizationStatus::Blocked))),
+        ],
+    );
+    assert_eq!(list.send_local_list(&full).status, UpdateStatus::Accepted);
+    assert_eq!(list.get_local_list_version().list_version, 5);
+
+    // A missing idTagInfo deletes the entry in a differential update
+    let differential = update(
+        6,
+        UpdateType::Differential,
+        vec![
+            entry("B", None),
+            entry("C", Some(info(AuthorizationStatus::Accepted))),
+        ],
+    );
+    assert_eq!(
+        list.send_local_list(&differential).status,
+        UpdateStatus::Accepted
+    );
+    assert!(list.get("B").is_none());
+    assert_eq!(list.len(), 2);
+    assert_eq!(
+        list.send_local_list(&differential).status,
+        UpdateStatus::VersionMismatch
+    );
+
+    // Rejected updates leave the list unchanged
+    let over_capacity = update(
+        7,
+        UpdateType::Differential,
+        vec![
+            entry("D", Some(info(AuthorizationStatus::Accepted))),
+            entry("E", Some(info(AuthorizationStatus::Accepted))),
+        ],
+    );
+    assert_eq!(
+        list.send_local_list(&over_capacity).status,
+        UpdateStatus::Failed
+    );
+    let duplicate = update(
+        7,
+        UpdateType::Full,
+        vec![
+            entry("A", Some(info(AuthorizationStatus::Accepted))),
+            entry("A", Some(info(AuthorizationStatus::Blocked))),
+        ],
+    );
+    assert_eq!(
+        list.send_local_list(&duplicate).status,
+        UpdateStatus::Failed
+    );
+    let oversized = update(
+        7,
+        UpdateType::Differential,
+        vec![entry("D", None), entry("E", None), entry("F", None)],
+    );
+    assert_eq!(
+        list.send_local_list(&oversized).status,
+        UpdateStatus::Failed
+    );
+    let incomplete = update(7, UpdateType::Full, vec![entry("A", None)]);
+    assert_eq!(
+        list.send_local_list(&incomplete).status,
+        UpdateStatus::Failed
+    );
+    assert_eq!(list.version(), 6);
+
+    // The Central System sends only what changed between two snapshots
+    let sent: LocalAuthList = [
+        (String::from("A"), info(AuthorizationStatus::Accepted)),
+        (String::from("C"), info(AuthorizationStatus::Accepted)),
+    ]
+    .into_iter()
+    .collect::<LocalAuthList>()
+    .with_version(6);
+    let target: LocalAuthList = [
+        (String::from("A"), info(AuthorizationStatus::Accepted)),
+        (String::from("C"), info(AuthorizationStatus::Expired)),
+        (String::from("D"), info(AuthorizationStatus::Accepted)),
+    ]
+    .into_iter()
+    .collect::<LocalAuthList>()
+    .with_version(7);
+    let req = sent.differential_update(&target);
+    let ids: Vec<_> = req
+        .local_authorization_list
+        .iter()
+        .flatten()
+        .map(|e| e.id_tag.as_str())
+        .collect();
+    assert_eq!(ids, ["C", "D"]);
+    assert_eq!(list.send_local_list(&req).status, UpdateStatus::Accepted);
+    assert_eq!(
+        list.get("C").map(|info| &info.status),
+        Some(&AuthorizationStatus::Expired)
+    );
+    assert!(target
+        .differential_update(&sent)
+        .local_authorization_list
+        .is_some_and(|updates| updates.len() == 2
+            && updates
+                .iter()
+                .any(|e| e.id_tag == "D" && e.id_tag_info.is_none())));
+}
+
+#[test]
+fn test_authorization_cache() {
+    use chrono::Duration;
+
+    use crate::{
+        AuthorizationCache, AuthorizationStatus, AuthorizeResponse, IdTagInfo,
+        LocalAuthList, LocalAuthorization, SimpleStatus,
+        StopTransactionResponse,
+    };
+
+    let info = |status, parent: Option<&str>| IdTagInfo {
+        expiry_date: None,
+        parent_id_tag: parent.map(String::from),
+        status,
+    };
+    let now = Utc::now();
+
+    let mut cache = AuthorizationCache::new(2);
+    cache.authorize_response(
+        "A",
+        &AuthorizeResponse {
+            id_tag_info: info(AuthorizationStatus::Accepted, Some("FLEET")),
+        },
+        None,
+        now,
+    );
+    cache.update(
+        "B",
+        &IdTagInfo {
+            expiry_date: Some(now - Duration::minutes(1)),
+            ..info(AuthorizationStatus::Accepted, Some("FLEET"))
+        },
+        None,
+        now,
+    );
+    assert!(cache.authorize_offline("A", None, now).is_allowed());
+    // Past its expiry date, B is reported as Expired
+    assert!(matches!(
+        cache.authorize_offline("B", None, now),
+        LocalAuthorization::NotAuthorized(IdTagInfo {
+            status: AuthorizationStatus::Expired,
+            ..
+        })
+    ));
+    assert_eq!(
+        cache.authorize_offline("C", None, now),
+        LocalAuthorization::Unknown
+    );
+
+    // A transaction started by A may be stopped by B of the same group
+    assert!(cache.is_same_group("A", "B", None));
+    assert!(!cache.is_same_group("A", "C", None));
+    // as may the parent id tag itself
+    assert!(cache.is_same_group("A", "FLEET", None));
+    assert!(cache.is_same_group("FLEET", "B", None));
+    assert!(!cache.is_same_group("C", "FLEET", None));
+
+    // The expired B is evicted before the more recently used A
+    cache.update("C", &info(AuthorizationStatus::Accepted, None), None, now);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("A").is_some());
+    assert!(cache.get("B").is_none());
+
+    // A StopTransaction.conf without idTagInfo changes nothing
+    cache.stop_transaction_response(
+        "C",
+        &StopTransactionResponse { id_tag_info: None },
+        None,
+        now,
+    );
+    cache.stop_transaction_response(
+        "C",
+        &StopTransactionResponse {
+            id_tag_info: Some(info(AuthorizationStatus::Blocked, None)),
+        },
+        None,
+        now,
+    );
+    assert!(!cache.authorize_offline("C", None, now).is_allowed());
+
+    // The Local Authorization List takes precedence over the cache
+    let list: LocalAuthList = [(
+        String::from("C"),
+        info(AuthorizationStatus::Accepted, Some("FLEET")),
+    )]
+    .into_iter()
+    .collect();
+    assert!(cache.authorize_offline("C", Some(&list), now).is_allowed());
+    assert!(cache.is_same_group("A", "C", Some(&list)));
+
+    // Identifiers in the Local Authorization List are not added to the cache
+    let mut cache = AuthorizationCache::new(1);
+    cache.update("A", &info(AuthorizationStatus::Accepted, None), None, now);
+    cache.authorize_response(
+        "C",
+        &AuthorizeResponse {
+            id_tag_info: info(AuthorizationStatus::Accepted, Some("FLEET")),
+        },
+        Some(&list),
+        now,
+    );
+    assert!(cache.get("C").is_none());
+    assert!(cache.get("A").is_some());
+
+    assert_eq!(cache.clear_cache().status, SimpleStatus::Accepted);
+    assert!(cache.is_empty());
+
+    let mut cache = AuthorizationCache::new(10)
+        .local_authorize_offline(false)
+        .allow_offline_tx_for_unknown_id(true);
+    cache.update("A", &info(AuthorizationStatus::Accepted, None), None, now);
+    assert_eq!(
+        cache.authorize_offline("A", None, now),
+        LocalAuthorization::Unknown
+    );
+    assert_eq!(
+        cache.authorize_offline("B", None, now),
+        LocalAuthorization::AllowedUnknown
+    );
+
+    // A disabled cache stores nothing
+    let mut cache = AuthorizationCache::new(10).enabled(false);
+    cache.update("A", &info(AuthorizationStatus::Accepted, None), None, now);
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_configuration() -> Result<(), Box<dyn std::error::Error>> {
+    use crate::{
+        ChangeConfigurationRequest, ChangeConfigurationStatus,
+        ConfigurationKey, ConfigurationStore, ConfigurationValue,
+        FeatureProfile, GetConfigurationRequest, OCPPCallErrorCode,
+        SampledMeasurand, MAX_AUTHORIZATION_KEY_LENGTH,
+    };
+
+    // The keys of a GetConfiguration.req are a list
+    let req = GetConfigurationRequest {
+        key: Some(vec![
+            String::from("HeartbeatInterval"),
+            String::from("AuthorizationKey"),
+            String::from("Vendor"),
+        ]),
+    };
+    req.validate()?;
+    assert_eq!(
+        serde_json::to_string(&req)?,
+        "{\"key\":[\"HeartbeatInterval\",\"AuthorizationKey\",\"Vendor\"]}"
+    );
+
+    assert_eq!(
+        ConfigurationKey::MeterValuesSampledData
+            .parse(" Energy.Active.Import.Register , SoC")?,
+        ConfigurationValue::Measurands(vec![
+            SampledMeasurand::EnergyActiveImportRegister,
+            SampledMeasurand::SoC,
+        ])
+    );
+    assert!(ConfigurationKey::MeterValuesSampledData
+        .parse("Energy.Active.Import")
+        .is_err());
+    assert_eq!(
+        ConfigurationKey::StopTxnSampledData.parse("")?,
+        ConfigurationValue::Measurands(Vec::new())
+    );
+    assert!(ConfigurationKey::ConnectorPhaseRotation
+        .parse("0.RST, 1.NotApplicable")
+        .is_ok());
+    assert!(ConfigurationKey::ConnectorPhaseRotation.parse("RST").is_err());
+    assert!(ConfigurationKey::SupportedFeatureProfiles
+        .parse("Core,SmartCharging")
+        .is_ok());
+    assert!(ConfigurationKey::SupportedFeatureProfiles
+        .parse("Core,Security")
+        .is_err());
+    assert_eq!(
+        ConfigurationKey::LocalAuthListEnabled.parse("TRUE")?,
+        ConfigurationValue::Boolean(true)
+    );
+    assert!(ConfigurationKey::HeartbeatInterval.parse("-1").is_err());
+    assert!(ConfigurationKey::AuthorizationKey
+        .parse(&"x".repeat(MAX_AUTHORIZATION_KEY_LENGTH + 1))
+        .is_err());
+    assert!(ConfigurationKey::AuthorizationKey.parse("short key").is_err());
+    assert_eq!(
+        ConfigurationKey::ReserveConnectorZeroSupported.profile(),
+        FeatureProfile::Reservation
+    );
+    assert!(ConfigurationKey::NumberOfConnectors.is_read_only());
+
+    let mut store = ConfigurationStore::default()
+        .reboot_required(ConfigurationKey::CpoName);
+    store.insert(
+        ConfigurationKey::HeartbeatInterval,
+        ConfigurationValue::Integer(300),
+    )?;
+    store.insert(
+        ConfigurationKey::NumberOfConnectors,
+        ConfigurationValue::Integer(2),
+    )?;
+    store.insert(
+        ConfigurationKey::MeterValuesSampledDataMaxLength,
+        ConfigurationValue::Integer(2),
+    )?;
+    store.insert(
+        ConfigurationKey::MeterValuesSampledData,
+        ConfigurationValue::Measurands(Vec::new()),
+    )?;
+    store.insert(
+        ConfigurationKey::AuthorizationKey,
+        ConfigurationKey::AuthorizationKey.parse("0123456789abcdef")?,
+    )?;
+    store.insert(
+        ConfigurationKey::SecurityProfile,
+        ConfigurationValue::Integer(1),
+    )?;
+    store.support(ConfigurationKey::CpoName);
+    assert!(store
+        .insert(
+            ConfigurationKey::HeartbeatInterval,
+            ConfigurationValue::Boolean(true)
+        )
+        .is_err());
+
+    // Write-only values are never reported
+    let res = store.get_configuration(&req)?;
+    let keys = res.configuration_key.unwrap_or_default();
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys[0].value.as_deref(), Some("300"));
+    assert!(!keys[0].readonly);
+    assert_eq!(keys[1].key, "AuthorizationKey");
+    assert!(keys[1].value.is_none());
+    assert_eq!(res.unknown_key, Some(vec![String::from("Vendor")]));
+    let all =
+        store.get_configuration(&GetConfigurationRequest { key: None })?;
+    assert_eq!(all.configuration_key.map(|keys| keys.len()), Some(7));
+    assert!(all.unknown_key.is_none());
+
+    // Requests for more keys than GetConfigurationMaxKeys are refused
+    let mut limited = store.clone();
+    limited.insert(
+        ConfigurationKey::GetConfigurationMaxKeys,
+        ConfigurationValue::Integer(2),
+    )?;
+    let two = GetConfigurationRequest {
+        key: Some(vec![
+            String::from("HeartbeatInterval"),
+            String::from("CpoName"),
+        ]),
+    };
+    assert!(limited.get_configuration(&two).is_ok());
+    assert!(matches!(
+        limited.get_configuration(&req),
+        Err(OCPPCallErrorCode::OccurenceConstraintViolation)
+    ));
+
+    // Keys are case-insensitive, and reported with their standard name
+    let res = store.get_configuration(&GetConfigurationRequest {
+        key: Some(vec![
+            String::from("heartbeatInterval"),
+            String::from("CPONAME"),
+        ]),
+    })?;
+    assert!(res.unknown_key.is_none());
+    assert_eq!(
+        res.configuration_key
+            .unwrap_or_default()
+            .iter()
+            .map(|key| key.key.as_str())
+            .collect::<Vec<_>>(),
+        ["HeartbeatInterval", "CpoName"]
+    );
+
+    let mut change = |key: &str, value: &str| {
+        store
+            .change_configuration(&ChangeConfigurationRequest {
+                key: String::from(key),
+                value: String::from(value),
+            })
+            .status
+    };
+    assert_eq!(
+        change("HeartbeatInterval", "60"),
+        ChangeConfigurationStatus::Accepted
+    );
+    assert_eq!(
+        change("heartbeatinterval", "60"),
+        ChangeConfigurationStatus::Accepted
+    );
+    assert_eq!(
+        change("HeartbeatInterval", "often"),
+        ChangeConfigurationStatus::Rejected
+    );
+    assert_eq!(
+        change("NumberOfConnectors", "3"),
+        ChangeConfigurationStatus::Rejected
+    );
+    assert_eq!(
+        change(
+            "MeterValuesSampledData",
+            "Energy.Active.Import.Register,Power.Active.Import,SoC"
+        ),
+        ChangeConfigurationStatus::Rejected
+    );
+    assert_eq!(
+        change("MeterValuesSampledData", "Voltage,SoC"),
+        ChangeConfigurationStatus::Accepted
+    );
+    assert_eq!(
+        change("SecurityProfile", "0"),
+        ChangeConfigurationStatus::Rejected
+    );
+    assert_eq!(
+        change("SecurityProfile", "2"),
+        ChangeConfigurationStatus::Accepted
+    );
+    assert_eq!(
+        change("CpoName", "Operator"),
+        ChangeConfigurationStatus::RebootRequired
+    );
+    assert_eq!(
+        change("LocalAuthListEnabled", "true"),
+        ChangeConfigurationStatus::NotSupported
+    );
+    assert_eq!(
+        change("VendorKey", "1"),
+        ChangeConfigurationStatus::NotSupported
+    );
+
+    assert_eq!(
+        store.get(ConfigurationKey::HeartbeatInterval),
+        Some(&ConfigurationValue::Integer(60))
+    );
+    assert_eq!(
+        store
+            .get(ConfigurationKey::MeterValuesSampledData)
+            .map(ToString::to_string)
+            .as_deref(),
+        Some("Voltage,SoC")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_composite_schedule() -> Result<(), Box<dyn std::error::Error>> {
+    use chrono::{DateTime, Duration};
+
+    use crate::charging_profile::*;
+    use crate::{
+        composite_schedule, CompositeScheduleCalculator,
+        GetCompositeScheduleRequest,
+    };
+
+    let start: DateTime<Utc> = "2024-01-01T00:00:00Z".parse()?;
+    let limits = |schedule: ChargingSchedule| -> Vec<(u32, f32)> {
+        schedule
+            .charging_schedule_period
+            .iter()
+            .map(|period| (period.start_period, period.limit))
+            .collect()
+    };
+
+    // 16 A on three phases, for the first three hours
+    let max = ChargingProfile::builder(ChargingRateUnit::W)
+        .purpose(ChargingProfilePurpose::ChargePointMaxProfile)
+        .kind(ChargingProfileKind::Absolute)
+        .schedule_start(start)
+        .valid_to(start + Duration::hours(3))
+        .id(1)
+        .stack_level(0)
+        .add_period(0, 11040.0, None)
+        .build();
+    // Every day from 01:00 to 02:00
+    let daily = ChargingProfile::builder(ChargingRateUnit::A)
+        .purpose(ChargingProfilePurpose::TxDefaultProfile)
+        .kind(ChargingProfileKind::Recurring)
+        .recurrency_kind(RecurrencyKind::Daily)
+        .schedule_start(start - Duration::days(1) + Duration::hours(1))
+        .schedule_duration(3600)
+        .id(2)
+        .stack_level(0)
+        .add_period(0, 20.0, None)
+        .build();
+    // Higher stack level, from 00:30 to 01:10
+    let override_default = ChargingProfile::builder(ChargingRateUnit::A)
+        .purpose(ChargingProfilePurpose::TxDefaultProfile)
+        .kind(ChargingProfileKind::Absolute)
+        .schedule_start(start + Duration::minutes(30))
+        .schedule_duration(2400)
+        .id(3)
+        .stack_level(1)
+        .add_period(0, 10.0, None)
+        .build();
+    let profiles = vec![max, daily, override_default];
+
+    // Without a limit after the max profile expires, the connector maximum is reported
+    assert_eq!(
+        limits(composite_schedule(
+            &profiles,
+            start,
+            4 * 3600,
+            Some(ChargingRateUnit::A)
+        )),
+        vec![(0, 16.0), (1800, 10.0), (4200, 16.0), (10800, 32.0)]
+    );
+
+    // A TxProfile overrides the TxDefaultProfiles from the start of the transaction
+    let tx = ChargingProfile::builder(ChargingRateUnit::A)
+        .id(4)
+        .stack_level(0)
+        .transaction_id(1)
+        .add_period(0, 6.0, None)
+        .add_period(1800, 13.0, None)
+        .build();
+    let mut profiles = profiles;
+    profiles.push(tx);
+    let calculator = CompositeScheduleCalculator::default()
+        .transaction_start(start + Duration::minutes(50));
+    assert_eq!(
+        limits(calculator.calculate(
+            &profiles,
+            start,
+            4 * 3600,
+            Some(ChargingRateUnit::A)
+        )),
+        vec![(0, 16.0), (1800, 10.0), (3000, 6.0), (4800, 13.0)]
+    );
+
+    let req = GetCompositeScheduleRequest {
+        connector_id: 1,
+        duration: 4 * 3600,
+        charging_rate_unit: Some(ChargingRateUnit::W),
+    };
+    let res = calculator.get_composite_schedule(&req, &profiles, start);
+    res.validate()?;
+    assert_eq!(res.connector_id, Some(1));
+    let schedule = res.charging_schedule.expect("composite schedule");
+    assert_eq!(schedule.charging_rate_unit, ChargingRateUnit::W);
+    assert_eq!(
+        limits(schedule),
+        vec![(0, 11040.0), (1800, 6900.0), (3000, 4140.0), (4800, 8970.0)]
+    );
+
+    // A recurring profile does not apply before its first recurrence
+    let future = ChargingProfile::builder(ChargingRateUnit::A)
+        .purpose(ChargingProfilePurpose::TxDefaultProfile)
+        .kind(ChargingProfileKind::Recurring)
+        .recurrency_kind(RecurrencyKind::Daily)
+        .schedule_start(start + Duration::hours(2))
+        .id(5)
+        .stack_level(0)
+        .add_period(0, 20.0, None)
+        .build();
+    assert_eq!(
+        limits(composite_schedule(
+            &[future],
+            start,
+            4 * 3600,
+            Some(ChargingRateUnit::A)
+        )),
+        vec![(0, 32.0), (7200, 20.0)]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_charging_profile_store() -> Result<(), Box<dyn std::error::Error>> {
+    use crate::charging_profile::*;
+    use crate::{
+        ChargingProfileStatus, ChargingProfileStore, ClearChargeProfileStatus,
+        ClearChargingProfileRequest, CompositeScheduleCalculator,
+        GetCompositeScheduleRequest, SetChargingProfileRequest, SimpleStatus,
+    };
+
+    let profile = |id, purpose, stack_level, limit| {
+        ChargingProfile::builder(ChargingRateUnit::A)
+            .purpose(purpose)
+            .id(id)
+            .stack_level(stack_level)
+            .add_period(0, limit, None)
+            .build()
+    };
+    let set = |connector_id, cs_charging_profiles| SetChargingProfileRequest {
+        connector_id,
+        cs_charging_profiles,
+    };
+    let clear = |id, connector_id, charging_profile_purpose| {
+        ClearChargingProfileRequest {
+            id,
+            connector_id,
+            charging_profile_purpose,
+            stack_level: None,
+        }
+    };
+
+    let mut store = ChargingProfileStore::default()
+        .charge_profile_max_stack_level(5)
+        .max_charging_profiles_installed(3)
+        .number_of_connectors(2);
+    let max =
+        profile(1, ChargingProfilePurpose::ChargePointMaxProfile, 0, 16.0);
+    assert_eq!(
+        store.set_charging_profile(&set(1, max.clone()), None).status,
+        ChargingProfileStatus::Rejected
+    );
+    assert_eq!(
+        store.set_charging_profile(&set(0, max), None).status,
+        ChargingProfileStatus::Accepted
+    );
+    let default = |id, limit| {
+        profile(id, ChargingProfilePurpose::TxDefaultProfile, 0, limit)
+    };
+    store.set_charging_profile(&set(0, default(2, 10.0)), None);
+    store.set_charging_profile(&set(1, default(3, 12.0)), None);
+
+    // The TxDefaultProfile of connector 1 takes the place of the one of connector 0
+    let ids = |connector_id| -> Vec<u32> {
+        store
+            .profiles_for_connector(connector_id)
+            .iter()
+            .map(|p| p.charging_profile_id)
+            .collect()
+    };
+    assert_eq!(ids(1), vec![1, 3]);
+    assert_eq!(ids(2), vec![1, 2]);
+
+    // Same purpose and stack level on the same connector replaces
+    assert_eq!(
+        store.set_charging_profile(&set(1, default(4, 8.0)), None).status,
+        ChargingProfileStatus::Accepted
+    );
+    assert!(store.get(3).is_none());
+    assert_eq!(store.len(), 3);
+
+    // Limits of the Charge Point
+    let mut tx = profile(5, ChargingProfilePurpose::TxProfile, 1, 6.0);
+    tx.transaction_id = Some(42);
+    assert_eq!(
+        store.set_charging_profile(&set(1, tx.clone()), Some(42)).status,
+        ChargingProfileStatus::Rejected
+    );
+    assert_eq!(
+        store
+            .set_charging_profile(
+                &set(
+                    1,
+                    profile(
+                        4,
+                        ChargingProfilePurpose::TxDefaultProfile,
+                        6,
+                        8.0
+                    )
+                ),
+                None
+            )
+            .status,
+        ChargingProfileStatus::Rejected
+    );
+    assert_eq!(
+        store.set_charging_profile(&set(3, default(6, 8.0)), None).status,
+        ChargingProfileStatus::Rejected
+    );
+
+    let calculator = CompositeScheduleCalculator::default();
+    let req = GetCompositeScheduleRequest {
+        connector_id: 1,
+        duration: 3600,
+        charging_rate_unit: None,
+    };
+    let res = store.get_composite_schedule(&req, &calculator, Utc::now());
+    assert_eq!(res.status, SimpleStatus::Accepted);
+    assert_eq!(
+        res.charging_schedule
+            .map(|schedule| schedule.charging_schedule_period[0].limit),
+        Some(8.0)
+    );
+    let unknown = GetCompositeScheduleRequest {
+        connector_id: 3,
+        ..req
+    };
+    assert_eq!(
+        store.get_composite_schedule(&unknown, &calculator, Utc::now()).status,
+        SimpleStatus::Rejected
+    );
+
+    assert_eq!(
+        store.clear_charging_profile(&clear(Some(99), None, None)).status,
+        ClearChargeProfileStatus::Unknown
+    );
+    assert_eq!(
+        store
+            .clear_charging_profile(&clear(
+                None,
+                Some(1),
+                Some(ChargingProfilePurpose::TxDefaultProfile)
+            ))
+            .status,
+        ClearChargeProfileStatus::Accepted
+    );
+    assert_eq!(store.len(), 2);
+
+    // TxProfiles apply to the active transaction and end with it
+    assert_eq!(
+        store.set_charging_profile(&set(1, tx.clone()), None).status,
+        ChargingProfileStatus::Rejected
+    );
+    assert_eq!(
+        store.set_charging_profile(&set(1, tx.clone()), Some(41)).status,
+        ChargingProfileStatus::Rejected
+    );
+    assert_eq!(
+        store.set_charging_profile(&set(1, tx), Some(42)).status,
+        ChargingProfileStatus::Accepted
+    );
+    store.transaction_ended(41);
+    assert!(store.get(5).is_some());
+    store.transaction_ended(42);
+    assert!(store.get(5).is_none());
+
+    assert_eq!(
+        store.clear_charging_profile(&clear(None, None, None)).status,
+        ClearChargeProfileStatus::Accepted
+    );
+    assert!(store.is_empty());
+
+    Ok(())
+}
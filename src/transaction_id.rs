@@ -0,0 +1,198 @@
+//! Allocation of transaction ids for StartTransaction.conf
+//!
+//! The transaction id returned in a StartTransaction.conf must be unique across all Charge Points of a Central System, including
+//! across restarts. A Charge Point which did not receive the StartTransaction.conf, e.g. because the connection dropped, sends
+//! the same StartTransaction.req again, which must be answered with the id it was already given.
+//!
+//! [MemoryTransactionIdAllocator] hands out ids from an in-memory counter and is suitable when ids need not survive a restart.
+//! [StoredTransactionIdAllocator] persists its counter and answers through a [TransactionIdStore].
+
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::sync::{Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+
+use crate::StartTransactionRequest;
+
+/// Number of answered StartTransaction.req remembered by [MemoryTransactionIdAllocator]
+pub const ANSWERED_STARTS_WINDOW: usize = 1024;
+
+/// Identity of a StartTransaction.req, used to recognise a request which is sent again
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StartTransactionKey {
+    /// Charge point which sent the request
+    pub charge_point_id: String,
+    /// Connector of the transaction
+    pub connector_id: u32,
+    /// Identifier which started the transaction
+    pub id_tag: String,
+    /// Meter reading at start in Wh
+    pub meter_start: i32,
+    /// Time at which the transaction started
+    pub timestamp: DateTime<Utc>,
+}
+
+impl StartTransactionKey {
+    /// Key of a StartTransaction.req sent by `charge_point_id`
+    pub fn new(charge_point_id: &str, req: &StartTransactionRequest) -> Self {
+        StartTransactionKey {
+            charge_point_id: String::from(charge_point_id),
+            connector_id: req.connector_id,
+            id_tag: req.id_tag.clone(),
+            meter_start: req.meter_start,
+            timestamp: req.timestamp,
+        }
+    }
+}
+
+/// Source of transaction ids for StartTransaction.conf
+pub trait TransactionIdAllocator: Send + Sync {
+    /// Transaction id for a StartTransaction.req. A request which was already answered gets the same id again.
+    fn allocate(&self, key: &StartTransactionKey) -> io::Result<u32>;
+}
+
+/// Allocates transaction ids from an in-memory counter, remembering the last [ANSWERED_STARTS_WINDOW] answers
+#[derive(Debug)]
+pub struct MemoryTransactionIdAllocator {
+    answered: Mutex<AnsweredStarts>,
+}
+
+impl MemoryTransactionIdAllocator {
+    /// Create an allocator whose first id is `start`
+    pub fn new(start: u32) -> Self {
+        MemoryTransactionIdAllocator {
+            answered: Mutex::new(AnsweredStarts {
+                next: start,
+                ids: HashMap::new(),
+                order: VecDeque::new(),
+            }),
+        }
+    }
+}
+
+impl Default for MemoryTransactionIdAllocator {
+    /// Starts at 1
+    fn default() -> Self { MemoryTransactionIdAllocator::new(1) }
+}
+
+impl TransactionIdAllocator for MemoryTransactionIdAllocator {
+    fn allocate(&self, key: &StartTransactionKey) -> io::Result<u32> {
+        let mut answered = lock(&self.answered);
+        if let Some(id) = answered.ids.get(key) {
+            return Ok(*id);
+        }
+
+        let id = answered.next;
+        answered.next = id.checked_add(1).ok_or_else(exhausted)?;
+        answered.insert(key.clone(), id);
+        Ok(id)
+    }
+}
+
+#[derive(Debug)]
+struct AnsweredStarts {
+    next: u32,
+    ids: HashMap<StartTransactionKey, u32>,
+    order: VecDeque<StartTransactionKey>,
+}
+
+impl AnsweredStarts {
+    fn insert(&mut self, key: StartTransactionKey, id: u32) {
+        if self.order.len() == ANSWERED_STARTS_WINDOW {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        self.order.push_back(key.clone());
+        self.ids.insert(key, id);
+    }
+}
+
+/// Persistence hooks of a [StoredTransactionIdAllocator], e.g. backed by a database
+pub trait TransactionIdStore: Send + Sync {
+    /// Highest id which may have been handed out, or 0 if none
+    fn high_water_mark(&self) -> io::Result<u32>;
+
+    /// Persist a new high water mark. Called before any id up to it is handed out.
+    fn set_high_water_mark(&self, id: u32) -> io::Result<()>;
+
+    /// Id given in answer to a StartTransaction.req, if it was answered before
+    fn answered(&self, key: &StartTransactionKey) -> io::Result<Option<u32>>;
+
+    /// Record the id given in answer to a StartTransaction.req
+    fn record_answer(
+        &self,
+        key: &StartTransactionKey,
+        id: u32,
+    ) -> io::Result<()>;
+}
+
+/// Allocates transaction ids which stay unique across restarts, persisting them through a [TransactionIdStore]
+///
+/// To avoid a write for every transaction, ids are reserved in blocks by raising the high water mark. After a restart,
+/// allocation continues above the persisted high water mark, so the unused ids of the last block are skipped.
+#[derive(Debug)]
+pub struct StoredTransactionIdAllocator<S> {
+    store: S,
+    block_size: u32,
+    counter: Mutex<StoredCounter>,
+}
+
+#[derive(Debug)]
+struct StoredCounter {
+    next: u32,
+    reserved: u32,
+}
+
+impl<S: TransactionIdStore> StoredTransactionIdAllocator<S> {
+    /// Create an allocator continuing above the high water mark of `store`
+    pub fn new(store: S) -> io::Result<Self> {
+        let reserved = store.high_water_mark()?;
+        Ok(StoredTransactionIdAllocator {
+            store,
+            block_size: 100,
+            counter: Mutex::new(StoredCounter {
+                next: reserved.checked_add(1).ok_or_else(exhausted)?,
+                reserved,
+            }),
+        })
+    }
+
+    /// Set the number of ids reserved with each write of the high water mark. Defaults to 100.
+    pub fn block_size(mut self, block_size: u32) -> Self {
+        self.block_size = block_size.max(1);
+        self
+    }
+
+    /// The underlying store
+    pub fn store(&self) -> &S { &self.store }
+}
+
+impl<S: TransactionIdStore> TransactionIdAllocator
+    for StoredTransactionIdAllocator<S>
+{
+    fn allocate(&self, key: &StartTransactionKey) -> io::Result<u32> {
+        // Held while checking and recording the answer, so that a request sent twice at once gets a single id
+        let mut counter = lock(&self.counter);
+        if let Some(id) = self.store.answered(key)? {
+            return Ok(id);
+        }
+
+        let id = counter.next;
+        if id > counter.reserved {
+            let reserved = id.saturating_add(self.block_size - 1);
+            self.store.set_high_water_mark(reserved)?;
+            counter.reserved = reserved;
+        }
+        counter.next = id.checked_add(1).ok_or_else(exhausted)?;
+        self.store.record_answer(key, id)?;
+        Ok(id)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn exhausted() -> io::Error { io::Error::other("transaction ids exhausted") }
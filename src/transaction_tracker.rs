@@ -0,0 +1,331 @@
+//! Tracking of transactions from StartTransaction.req, MeterValues.req and StopTransaction.req
+//!
+//! [TransactionTracker] keeps the state of each transaction reported by a Charge Point: who started it on which connector, the
+//! meter readings at start and stop, and the samples received in between. Transaction-related messages may be delivered late
+//! or more than once after the Charge Point was offline, so repeated messages are ignored and samples are kept in time order
+//! regardless of the order in which they arrive.
+//!
+//! Each message returns the [TransactionAnomaly]s it revealed. Anomalies do not prevent a message from being applied where
+//! possible, as a Central System is expected to answer transaction-related messages even when their content is inconsistent.
+//!
+//! # Example
+//! ```
+//! # fn ocpp_transaction_tracker_example() {
+//! use ocpp::*;
+//!
+//! let mut tracker = TransactionTracker::default();
+//! let start = StartTransactionRequest {
+//!     connector_id: 1,
+//!     id_tag: String::from("TAG"),
+//!     meter_start: 1000,
+//!     reservation_id: None,
+//!     timestamp: chrono::Utc::now(),
+//! };
+//! // The transaction id is assigned in the StartTransaction.conf
+//! assert!(tracker.start(42, &start).is_empty());
+//!
+//! let stop = StopTransactionRequest {
+//!     id_tag: None,
+//!     meter_stop: 8500,
+//!     timestamp: chrono::Utc::now(),
+//!     transaction_id: 42,
+//!     reason: Some(StopReason::Local),
+//!     transaction_data: None,
+//! };
+//! assert!(tracker.stop(&stop).is_empty());
+//! assert_eq!(tracker.get(42).and_then(Transaction::energy_delivered), Some(7500));
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::{
+    MeterValue, MeterValuesRequest, SampledContext, SampledMeasurand,
+    SampledUnit, StartTransactionRequest, StopReason, StopTransactionRequest,
+};
+
+/// Irregularity in the messages of a transaction
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TransactionAnomaly {
+    /// A transaction id was reused for a different StartTransaction.req, which is ignored
+    #[error("transaction {0} was already started with different details")]
+    DuplicateTransactionId(u32),
+    /// A transaction started on a connector which already has an active transaction
+    #[error(
+        "transaction {transaction_id} started on connector {connector_id} while transaction {active} is active"
+    )]
+    ConnectorInUse {
+        /// Connector of the new transaction
+        connector_id: u32,
+        /// New transaction
+        transaction_id: u32,
+        /// Transaction which is still active on the connector
+        active: u32,
+    },
+    /// A MeterValues.req or StopTransaction.req refers to a transaction which was never started
+    #[error("unknown transaction {0}")]
+    UnknownTransaction(u32),
+    /// A transaction was stopped again with different details, which are ignored
+    #[error("transaction {0} was already stopped with different details")]
+    ConflictingStop(u32),
+    /// The meter reading at stop is lower than at start
+    #[error(
+        "transaction {transaction_id} stopped at {meter_stop} Wh, below its start at {meter_start} Wh"
+    )]
+    MeterStopBelowMeterStart {
+        /// Transaction
+        transaction_id: u32,
+        /// Meter reading at start in Wh
+        meter_start: i32,
+        /// Meter reading at stop in Wh
+        meter_stop: u32,
+    },
+    /// The transaction data of a StopTransaction.req has no Transaction.Begin sample
+    #[error("transaction {0} has no Transaction.Begin sample")]
+    MissingTransactionBegin(u32),
+}
+
+/// Details of a StopTransaction.req
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionStop {
+    /// Identifier which stopped the transaction, if known
+    pub id_tag: Option<String>,
+    /// Meter reading at stop in Wh
+    pub meter_stop: u32,
+    /// Time at which the transaction stopped
+    pub timestamp: DateTime<Utc>,
+    /// Reason for stopping, `None` meaning Local
+    pub reason: Option<StopReason>,
+}
+
+/// State of a single transaction
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    /// Id assigned by the Central System in the StartTransaction.conf
+    pub transaction_id: u32,
+    /// Connector on which the transaction runs
+    pub connector_id: u32,
+    /// Identifier which started the transaction
+    pub id_tag: String,
+    /// Meter reading at start in Wh
+    pub meter_start: i32,
+    /// Time at which the transaction started
+    pub started_at: DateTime<Utc>,
+    /// Reservation ended by the transaction
+    pub reservation_id: Option<i32>,
+    /// Samples from MeterValues.req and the transaction data of the StopTransaction.req, in time order
+    pub meter_values: Vec<MeterValue>,
+    /// Stop of the transaction, `None` while it is active
+    pub stop: Option<TransactionStop>,
+}
+
+impl Transaction {
+    /// Whether the transaction has not been stopped
+    pub fn is_active(&self) -> bool { self.stop.is_none() }
+
+    /// Energy delivered in Wh. Once stopped this is the difference between the meter readings at stop and start. While active it
+    /// is estimated from the latest Energy.Active.Import.Register sample, if any.
+    pub fn energy_delivered(&self) -> Option<i64> {
+        let meter = match &self.stop {
+            Some(stop) => i64::from(stop.meter_stop),
+            None => self.latest_energy_register()?,
+        };
+        Some(meter - i64::from(self.meter_start))
+    }
+
+    /// Latest Energy.Active.Import.Register reading in Wh
+    fn latest_energy_register(&self) -> Option<i64> {
+        self.meter_values.iter().rev().find_map(|meter_value| {
+            meter_value.sampled_value.iter().find_map(|sample| {
+                let measurand = sample
+                    .measurand
+                    .as_ref()
+                    .unwrap_or(&SampledMeasurand::EnergyActiveImportRegister);
+                if *measurand != SampledMeasurand::EnergyActiveImportRegister
+                    || sample.phase.is_some()
+                {
+                    return None;
+                }
+                let value: f64 = sample.value.parse().ok()?;
+                let wh = match sample.unit {
+                    Some(SampledUnit::KWh) => value * 1000.0,
+                    _ => value,
+                };
+                Some(wh.round() as i64)
+            })
+        })
+    }
+
+    /// Add samples, keeping them in time order and skipping samples already received
+    fn add_meter_values(&mut self, meter_values: &[MeterValue]) {
+        for meter_value in meter_values {
+            if self.meter_values.contains(meter_value) {
+                continue;
+            }
+            let position = self
+                .meter_values
+                .partition_point(|m| m.timestamp <= meter_value.timestamp);
+            self.meter_values.insert(position, meter_value.clone());
+        }
+    }
+
+    fn has_sample(&self, context: &SampledContext) -> bool {
+        self.meter_values.iter().any(|meter_value| {
+            meter_value
+                .sampled_value
+                .iter()
+                .any(|sample| sample.context.as_ref() == Some(context))
+        })
+    }
+}
+
+/// State of the transactions of a Charge Point. See the [module documentation](self).
+#[derive(Debug, Clone, Default)]
+pub struct TransactionTracker {
+    transactions: HashMap<u32, Transaction>,
+}
+
+impl TransactionTracker {
+    /// A transaction, active or stopped
+    pub fn get(&self, transaction_id: u32) -> Option<&Transaction> {
+        self.transactions.get(&transaction_id)
+    }
+
+    /// Transactions which have not been stopped
+    pub fn active(&self) -> impl Iterator<Item = &Transaction> {
+        self.transactions.values().filter(|t| t.is_active())
+    }
+
+    /// The active transaction on a connector
+    pub fn active_on_connector(
+        &self,
+        connector_id: u32,
+    ) -> Option<&Transaction> {
+        self.active().find(|t| t.connector_id == connector_id)
+    }
+
+    /// Stop tracking a transaction, e.g. once it has been billed
+    pub fn remove(&mut self, transaction_id: u32) -> Option<Transaction> {
+        self.transactions.remove(&transaction_id)
+    }
+
+    /// Apply a StartTransaction.req, answered with `transaction_id` in the StartTransaction.conf
+    pub fn start(
+        &mut self,
+        transaction_id: u32,
+        req: &StartTransactionRequest,
+    ) -> Vec<TransactionAnomaly> {
+        if let Some(existing) = self.transactions.get(&transaction_id) {
+            let repeated = existing.connector_id == req.connector_id
+                && existing.id_tag == req.id_tag
+                && existing.meter_start == req.meter_start
+                && existing.started_at == req.timestamp;
+            if repeated {
+                return Vec::new();
+            }
+            return vec![TransactionAnomaly::DuplicateTransactionId(
+                transaction_id,
+            )];
+        }
+
+        let mut anomalies = Vec::new();
+        if let Some(active) = self.active_on_connector(req.connector_id) {
+            anomalies.push(TransactionAnomaly::ConnectorInUse {
+                connector_id: req.connector_id,
+                transaction_id,
+                active: active.transaction_id,
+            });
+        }
+        self.transactions.insert(
+            transaction_id,
+            Transaction {
+                transaction_id,
+                connector_id: req.connector_id,
+                id_tag: req.id_tag.clone(),
+                meter_start: req.meter_start,
+                started_at: req.timestamp,
+                reservation_id: req.reservation_id,
+                meter_values: Vec::new(),
+                stop: None,
+            },
+        );
+        anomalies
+    }
+
+    /// Apply a MeterValues.req. Samples which do not belong to a transaction are ignored.
+    pub fn meter_values(
+        &mut self,
+        req: &MeterValuesRequest,
+    ) -> Vec<TransactionAnomaly> {
+        let Some(transaction_id) = req.transaction_id else {
+            return Vec::new();
+        };
+        match self.transactions.get_mut(&transaction_id) {
+            Some(transaction) => {
+                transaction.add_meter_values(&req.meter_value);
+                Vec::new()
+            },
+            None => {
+                vec![TransactionAnomaly::UnknownTransaction(transaction_id)]
+            },
+        }
+    }
+
+    /// Apply a StopTransaction.req. A repeated StopTransaction.req is ignored.
+    pub fn stop(
+        &mut self,
+        req: &StopTransactionRequest,
+    ) -> Vec<TransactionAnomaly> {
+        let Some(transaction) = self.transactions.get_mut(&req.transaction_id)
+        else {
+            return vec![TransactionAnomaly::UnknownTransaction(
+                req.transaction_id,
+            )];
+        };
+
+        let stop = TransactionStop {
+            id_tag: req.id_tag.clone(),
+            meter_stop: req.meter_stop,
+            timestamp: req.timestamp,
+            reason: req.reason.clone(),
+        };
+        let mut anomalies = Vec::new();
+        match &transaction.stop {
+            Some(existing) if *existing == stop => {},
+            Some(_) => anomalies
+                .push(TransactionAnomaly::ConflictingStop(req.transaction_id)),
+            None => {
+                if i64::from(req.meter_stop)
+                    < i64::from(transaction.meter_start)
+                {
+                    anomalies.push(
+                        TransactionAnomaly::MeterStopBelowMeterStart {
+                            transaction_id: req.transaction_id,
+                            meter_start: transaction.meter_start,
+                            meter_stop: req.meter_stop,
+                        },
+                    );
+                }
+                transaction.stop = Some(stop);
+
+                let transaction_data =
+                    req.transaction_data.as_deref().unwrap_or_default();
+                transaction.add_meter_values(transaction_data);
+                if !transaction_data.is_empty()
+                    && !transaction
+                        .has_sample(&SampledContext::TransactionBegin)
+                {
+                    anomalies.push(
+                        TransactionAnomaly::MissingTransactionBegin(
+                            req.transaction_id,
+                        ),
+                    );
+                }
+            },
+        }
+        anomalies
+    }
+}